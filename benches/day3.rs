@@ -0,0 +1,137 @@
+//! Compares the `u64` bitsets of day 3 with the `HashSet<char>` compartments they
+//! replaced, on the day 3 input. Run with `cargo bench --bench day3`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc2022::bench::{BenchConfig, Timings};
+use aoc2022::day3::{Group, Rucksack};
+use aoc2022::input::InputSource;
+
+/// The `HashSet` representation, kept as it was to compare against.
+mod hashset {
+    use std::collections::HashSet;
+
+    use aoc2022::day3::Rucksack as Bitset;
+
+    pub struct Rucksack {
+        first: HashSet<char>,
+        second: HashSet<char>,
+    }
+
+    impl Rucksack {
+        pub fn new(line: &str) -> Rucksack {
+            let (first, second) = line.split_at(line.len() / 2);
+            Rucksack {
+                first: first.chars().collect(),
+                second: second.chars().collect(),
+            }
+        }
+
+        pub fn get_priorities(&self) -> u32 {
+            self.first
+                .intersection(&self.second)
+                .map(|&item| Bitset::priority(item))
+                .sum()
+        }
+    }
+
+    pub struct Group {
+        first: HashSet<char>,
+        second: HashSet<char>,
+        third: HashSet<char>,
+    }
+
+    impl Group {
+        pub fn new(first: &str, second: &str, third: &str) -> Group {
+            Group {
+                first: first.chars().collect(),
+                second: second.chars().collect(),
+                third: third.chars().collect(),
+            }
+        }
+
+        pub fn priority(&self) -> u32 {
+            let common: HashSet<char> = self.first.intersection(&self.second).copied().collect();
+            common
+                .intersection(&self.third)
+                .map(|&item| Bitset::priority(item))
+                .sum()
+        }
+    }
+}
+
+fn time(config: &BenchConfig, mut run: impl FnMut() -> u32) -> (u32, Timings) {
+    for _ in 0..config.warmup {
+        black_box(run());
+    }
+
+    let mut samples: Vec<Duration> = Vec::with_capacity(config.iterations);
+    let mut answer = 0;
+    for _ in 0..config.iterations {
+        let start = Instant::now();
+        answer = black_box(run());
+        samples.push(start.elapsed());
+    }
+    (answer, Timings::from_samples(&mut samples))
+}
+
+/// Solves a part of the puzzle from the lines of the input.
+type Solver = fn(&[&str]) -> u32;
+
+fn priorities(lines: &[&str]) -> u32 {
+    lines
+        .iter()
+        .map(|line| Rucksack::new(line).unwrap().get_priorities())
+        .sum()
+}
+
+fn badges(lines: &[&str]) -> u32 {
+    lines
+        .chunks_exact(3)
+        .map(|group| Group::new(group[0], group[1], group[2]).priority())
+        .sum()
+}
+
+fn hashset_priorities(lines: &[&str]) -> u32 {
+    lines
+        .iter()
+        .map(|line| hashset::Rucksack::new(line).get_priorities())
+        .sum()
+}
+
+fn hashset_badges(lines: &[&str]) -> u32 {
+    lines
+        .chunks_exact(3)
+        .map(|group| hashset::Group::new(group[0], group[1], group[2]).priority())
+        .sum()
+}
+
+fn main() {
+    let input = InputSource::resolve(3, None)
+        .read_to_string()
+        .expect("the day 3 input is readable");
+    let lines: Vec<&str> = input.lines().collect();
+    let config = BenchConfig::default();
+
+    println!(
+        "{:<10} | {:<7} | {:>10} | {:>10} | {:>10} | Answer",
+        "Phase", "Set", "min", "median", "p95"
+    );
+    println!("-----------+---------+------------+------------+------------+-------");
+    let phases: [(&str, &str, Solver); 4] = [
+        ("priorities", "bitset", priorities),
+        ("priorities", "hashset", hashset_priorities),
+        ("badges", "bitset", badges),
+        ("badges", "hashset", hashset_badges),
+    ];
+    for (phase, set, run) in phases {
+        let (answer, timings) = time(&config, || run(black_box(&lines)));
+        println!(
+            "{phase:<10} | {set:<7} | {:>10.1?} | {:>10.1?} | {:>10.1?} | {answer}",
+            Duration::from_nanos(timings.min_ns),
+            Duration::from_nanos(timings.median_ns),
+            Duration::from_nanos(timings.p95_ns),
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::input::input_dir;
+use crate::{Error, Result};
+
+/// Name of the registry file, stored next to the puzzle inputs.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// An expected answer, written as a number or as a string in the registry.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// The expected answers of a single input. Either part may be missing.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+pub struct ExpectedAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+impl ExpectedAnswers {
+    pub fn expected(&self, part: u8) -> Option<String> {
+        let answer = match part {
+            1 => self.part1.as_ref(),
+            _ => self.part2.as_ref(),
+        };
+        answer.map(Answer::to_string)
+    }
+}
+
+/// Reads answers written in TOML.
+fn from_toml<T: DeserializeOwned>(s: &str) -> Result<T> {
+    toml::from_str(s).map_err(|err| Error::config(err.to_string()))
+}
+
+impl std::str::FromStr for ExpectedAnswers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ExpectedAnswers> {
+        from_toml(s)
+    }
+}
+
+/// Registry of the known answers of each day and part, like:
+///
+/// ```toml
+/// [day5]
+/// part1 = "CMZ"
+/// part2 = "MCD"
+/// ```
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct KnownAnswers(BTreeMap<String, ExpectedAnswers>);
+
+impl KnownAnswers {
+    /// The registry next to the puzzle inputs.
+    pub fn default_path() -> PathBuf {
+        input_dir().join(ANSWERS_FILE)
+    }
+
+    /// Loads a registry, which is empty if the file does not exist.
+    pub fn load(path: &Path) -> Result<KnownAnswers> {
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse().map_err(|err: Error| err.in_file(path)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(KnownAnswers::default()),
+            Err(err) => Err(Error::from(err).in_file(path)),
+        }
+    }
+
+    pub fn expected(&self, day: u8, part: u8) -> Option<String> {
+        self.0.get(&format!("day{day}"))?.expected(part)
+    }
+}
+
+impl std::str::FromStr for KnownAnswers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<KnownAnswers> {
+        from_toml(s)
+    }
+}
+
+/// Outcome of checking an answer against the registry.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<String>, answer: &str) -> Verdict {
+        match expected {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {expected})"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::answers::{KnownAnswers, Verdict, ANSWERS_FILE};
+    use crate::puzzles;
+
+    #[test]
+    fn can_read_known_answers() {
+        let answers: KnownAnswers = "[day5]\npart1 = \"CMZ\"\n\n[day6]\npart2 = 19\n"
+            .parse()
+            .unwrap();
+
+        assert_eq!(Some("CMZ".to_owned()), answers.expected(5, 1));
+        assert_eq!(None, answers.expected(5, 2));
+        assert_eq!(Some("19".to_owned()), answers.expected(6, 2));
+        assert_eq!(None, answers.expected(7, 1));
+    }
+
+    #[test]
+    fn can_check_answers() {
+        assert_eq!(Verdict::Pass, Verdict::check(Some("7".to_owned()), "7"));
+        assert_eq!(
+            Verdict::Fail {
+                expected: "7".to_owned()
+            },
+            Verdict::check(Some("7".to_owned()), "0")
+        );
+        assert_eq!(Verdict::Unknown, Verdict::check(None, "7"));
+    }
+
+    #[test]
+    fn solutions_match_known_answers() {
+        let inputs = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+        let answers = KnownAnswers::load(&inputs.join(ANSWERS_FILE)).unwrap();
+
+        for puzzle in puzzles() {
+            let input = std::fs::read(inputs.join(format!("input{}", puzzle.day()))).unwrap();
+            let parsed = puzzle.parse(&mut input.as_slice()).unwrap();
+
+            let day = puzzle.day();
+            assert_eq!(
+                Verdict::Pass,
+                Verdict::check(answers.expected(day, 1), &parsed.part1())
+            );
+            assert_eq!(
+                Verdict::Pass,
+                Verdict::check(answers.expected(day, 2), &parsed.part2())
+            );
+
+            let (part1, part2) = puzzle.stream(&mut input.as_slice()).unwrap();
+            assert_eq!(
+                Verdict::Pass,
+                Verdict::check(answers.expected(day, 1), &part1)
+            );
+            assert_eq!(
+                Verdict::Pass,
+                Verdict::check(answers.expected(day, 2), &part2)
+            );
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::solution::Puzzle;
+use crate::{Error, Result};
+
+/// Differences below this are treated as noise when looking for regressions.
+const NOISE_FLOOR_NS: u64 = 1_000;
+
+/// How many times each phase of a puzzle is run.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    /// Untimed runs done before measuring, so caches and allocators are warm.
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 10,
+            iterations: 100,
+        }
+    }
+}
+
+/// Summary of the samples taken for a single phase, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Timings {
+    pub fn from_samples(samples: &mut [Duration]) -> Timings {
+        samples.sort();
+        let nanos = |index: usize| {
+            samples
+                .get(index.min(samples.len().saturating_sub(1)))
+                .map_or(0, |sample| sample.as_nanos() as u64)
+        };
+
+        Timings {
+            min_ns: nanos(0),
+            median_ns: nanos(samples.len() / 2),
+            p95_ns: nanos((samples.len() * 95).div_ceil(100).saturating_sub(1)),
+        }
+    }
+}
+
+/// The timings of every phase of a puzzle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayTimings {
+    pub day: u8,
+    pub parse: Timings,
+    pub part1: Timings,
+    pub part2: Timings,
+}
+
+impl DayTimings {
+    pub fn phases(&self) -> [(&'static str, Timings); 3] {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+/// Times parsing the input of a puzzle and solving each of its parts.
+pub fn bench(puzzle: &dyn Puzzle, input: &str, config: &BenchConfig) -> Result<DayTimings> {
+    for _ in 0..config.warmup {
+        let parsed = puzzle.parse(&mut input.as_bytes())?;
+        black_box(parsed.part1());
+        black_box(parsed.part2());
+    }
+
+    let mut parse = Vec::with_capacity(config.iterations);
+    let mut part1 = Vec::with_capacity(config.iterations);
+    let mut part2 = Vec::with_capacity(config.iterations);
+    for _ in 0..config.iterations {
+        let start = Instant::now();
+        let parsed = puzzle.parse(&mut black_box(input.as_bytes()))?;
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(parsed.part1());
+        part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(parsed.part2());
+        part2.push(start.elapsed());
+    }
+
+    Ok(DayTimings {
+        day: puzzle.day(),
+        parse: Timings::from_samples(&mut parse),
+        part1: Timings::from_samples(&mut part1),
+        part2: Timings::from_samples(&mut part2),
+    })
+}
+
+/// A phase whose median got slower than in the baseline.
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub phase: &'static str,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+/// Timings saved from a previous run, to compare later runs against.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub days: Vec<DayTimings>,
+}
+
+impl Baseline {
+    /// Loads a baseline, or returns `None` if it has not been saved yet.
+    pub fn load(path: &Path) -> Result<Option<Baseline>> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let baseline = serde_json::from_str(&content)
+                    .map_err(|err| Error::config(err.to_string()).in_file(path))?;
+                Ok(Some(baseline))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(Error::from(err).in_file(path)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::from)?;
+        fs::write(path, content + "\n")?;
+        Ok(())
+    }
+
+    /// Records the timings of a day, replacing the previous ones.
+    pub fn update(&mut self, timings: DayTimings) {
+        self.days.retain(|day| day.day != timings.day);
+        self.days.push(timings);
+        self.days.sort_by_key(|day| day.day);
+    }
+
+    /// Phases of `current` whose median grew more than `threshold` (0.1 is 10%).
+    pub fn regressions(&self, current: &DayTimings, threshold: f64) -> Vec<Regression> {
+        let Some(baseline) = self.days.iter().find(|day| day.day == current.day) else {
+            return vec![];
+        };
+
+        baseline
+            .phases()
+            .into_iter()
+            .zip(current.phases())
+            .filter(|((_, before), (_, now))| {
+                let limit = before.median_ns as f64 * (1.0 + threshold);
+                now.median_ns as f64 > limit && now.median_ns - before.median_ns > NOISE_FLOOR_NS
+            })
+            .map(|((phase, before), (_, now))| Regression {
+                day: current.day,
+                phase,
+                baseline_ns: before.median_ns,
+                current_ns: now.median_ns,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use crate::bench::{Baseline, DayTimings, Regression, Timings};
+
+    fn timings(median_ns: u64) -> Timings {
+        Timings {
+            min_ns: median_ns,
+            median_ns,
+            p95_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn can_summarize_samples() {
+        let mut samples: Vec<Duration> = (1..=100).rev().map(Duration::from_nanos).collect();
+        let timings = Timings::from_samples(&mut samples);
+
+        assert_eq!(1, timings.min_ns);
+        assert_eq!(51, timings.median_ns);
+        assert_eq!(95, timings.p95_ns);
+    }
+
+    #[test]
+    fn can_find_regressions() {
+        let baseline = Baseline {
+            days: vec![DayTimings {
+                day: 4,
+                parse: timings(100_000),
+                part1: timings(100_000),
+                part2: timings(100),
+            }],
+        };
+        let current = DayTimings {
+            day: 4,
+            parse: timings(105_000),
+            part1: timings(150_000),
+            part2: timings(900),
+        };
+
+        let expected = vec![Regression {
+            day: 4,
+            phase: "part1",
+            baseline_ns: 100_000,
+            current_ns: 150_000,
+        }];
+        assert_eq!(expected, baseline.regressions(&current, 0.1));
+
+        let other_day = DayTimings { day: 5, ..current };
+        assert!(baseline.regressions(&other_day, 0.1).is_empty());
+    }
+
+    #[test]
+    fn reports_the_baseline_file() {
+        let path = std::env::temp_dir().join("aoc2022-bench-baseline.json");
+        fs::write(&path, "{\"days\": [}").unwrap();
+        let err = Baseline::load(&path).unwrap_err();
+        assert_eq!(
+            format!("{}: expected value at line 1 column 11", path.display()),
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,987 @@
+use std::env;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use aoc2022::answers::{KnownAnswers, Verdict};
+use aoc2022::bench::{self, Baseline, BenchConfig};
+use aoc2022::day1::parallel;
+use aoc2022::day1::planner::{Item, Plan, Strategy};
+use aoc2022::day1::stats::Statistics;
+use aoc2022::day1::{Calories, Inventory};
+use aoc2022::day2::analysis::Analysis;
+use aoc2022::day2::breakdown::Breakdown;
+use aoc2022::day2::cipher::EncodedGuide;
+use aoc2022::day2::decoder::{Decoders, GuideDecoder};
+use aoc2022::day2::league::League;
+use aoc2022::day2::rules::{Column, Rules};
+use aoc2022::day2::scoring::Scoring;
+use aoc2022::day2::simulation::{PlayerSpec, Simulation};
+use aoc2022::day2::Tournament;
+use aoc2022::input::InputSource;
+use aoc2022::log;
+use aoc2022::output::{self, Format};
+use aoc2022::solution::{self, PartAnswer, Puzzle};
+
+const USAGE: &str = "usage: aoc [-v | -vv | --quiet] <COMMAND>...
+
+       aoc all [--format <text|json>]
+       aoc --day <N> [--part <1|2>] [--input <PATH>] [--format <text|json>] [--stream]
+       aoc bench [--day <N>] [--iterations <N>] [--warmup <N>] [--baseline <PATH>]
+                 [--threshold <PERCENT>] [--save-baseline]
+       aoc verify [--day <N>] [--answers <PATH>]
+       aoc stats [--input <PATH>] [--bins <N>]
+       aoc plan --elves <K> [--strategy <auto|exact|lpt|kk>] [--input <PATH>] [--items]
+       aoc top [-n <N>] [--threads <N>] [--width <u32|u64|u128>] [--input <PATH>]
+       aoc score [--scoring <PATH> | --rules <classic|rpsls>] [--decoder <NAME>]
+                 [--input <PATH>]
+       aoc cipher --score <N> [--input <PATH>]
+       aoc analyze [--scoring <PATH>] [--decoder <NAME>] [--input <PATH>]
+       aoc simulate <PLAYER> <PLAYER> [--matches <N>] [--rounds <N>] [--seed <N>]
+                    [--scoring <PATH>]
+       aoc breakdown [--scoring <PATH>] [--decoder <NAME>] [--input <PATH>] [--csv]
+                     [--summary]
+       aoc league <GUIDE> <GUIDE>... [--scoring <PATH>]
+
+`-v` logs how the inputs are parsed, `-vv` also traces the intermediate states, and
+`--quiet` only prints the errors. The logs go to stderr.
+`--format json` prints one {day, part, answer, elapsed_ms} record per line.
+The input defaults to $AOC_INPUT_DIR/inputN, or inputs/inputN. Use `--input -` to read stdin.
+`--stream` solves both parts in a single pass, without keeping the whole input in memory.
+`bench` saves its baseline the first time it runs, and flags the phases that got slower
+on later runs. Build with --release to get meaningful timings.
+`verify` checks the answers against the registry in $AOC_INPUT_DIR/answers.toml, or
+inputs/answers.toml.
+`stats` summarizes the calories carried by the elves of day 1.
+`plan` redistributes the items of day 1 between K elves, so the heaviest one carries as
+little as possible, and reports how far the plan is from the lower bound. `--items` also
+lists the items given to each elf.
+`top` lists the N elves of day 1 carrying the most calories, reading the input file on
+several threads. It defaults to the top 3, and to a thread per CPU. The calories are
+summed with u64 unless `--width` says otherwise, and sums that overflow are errors.
+`score` scores both readings of the day 2 strategy guide with the points and letters of a
+scoring file, read as TOML if it ends with .toml and as `section.key = value` lines
+otherwise. Whatever the file leaves out keeps the rules of the puzzle. `--decoder` only
+scores one reading of the second column: `shape` (part 1) or `outcome` (part 2).
+`--rules rpsls` scores the guide as rock paper scissors lizard Spock instead, where the
+oponent plays A to E and the player V to Z, in the order rock, paper, scissors, lizard and
+Spock, or X, Y and Z for the outcomes.
+`cipher` lists what X, Y and Z may stand for in a day 2 guide claimed to score N points:
+three different shapes, or three different outcomes.
+`analyze` compares the score of a day 2 guide, read with the `shape` decoder unless
+`--decoder` says otherwise, with the best and worst scores against the same shapes, and
+with the Nash equilibrium of the game.
+`simulate` plays matches of day 2 rounds between two players, each one of `frequency`,
+`markov`, `wsls` (win-stay, lose-shift), `random` or `guide=PATH` for the shapes of a
+guide file. It defaults to 100 matches of 1000 rounds, and to the seed 0.
+`breakdown` lists the rounds of a day 2 guide with their points and the running score,
+then the wins, draws and losses of each shape. `--csv` prints CSV instead of a table, and
+`--summary` only prints the shapes.
+`league` has every day 2 guide play every other one, round by round, for as many rounds
+as the shorter guide. A match won is worth 3 points and a draw 1. Guides tied on points
+are ranked on the points won between them, then on the difference of rounds won and
+lost, the rounds won, and the score.";
+
+const DEFAULT_BASELINE: &str = "bench-baseline.json";
+
+const DEFAULT_BINS: usize = 10;
+
+const DEFAULT_SIMULATION: Simulation = Simulation {
+    matches: 100,
+    rounds: 1000,
+    seed: 0,
+};
+
+enum Command {
+    All {
+        format: Format,
+    },
+    Day {
+        puzzle: &'static dyn Puzzle,
+        part: Option<u8>,
+        input: Option<String>,
+        format: Format,
+        stream: bool,
+    },
+    Bench {
+        puzzles: Vec<&'static dyn Puzzle>,
+        config: BenchConfig,
+        baseline: PathBuf,
+        /// Regression threshold, where 0.1 means 10% slower.
+        threshold: f64,
+        save_baseline: bool,
+    },
+    Verify {
+        puzzles: Vec<&'static dyn Puzzle>,
+        answers: PathBuf,
+    },
+    Stats {
+        input: Option<String>,
+        bins: usize,
+    },
+    Plan {
+        input: Option<String>,
+        elves: usize,
+        strategy: Strategy,
+        show_items: bool,
+    },
+    Top {
+        input: Option<String>,
+        n: usize,
+        threads: usize,
+        width: Width,
+    },
+    Score {
+        input: Option<String>,
+        scoring: Option<PathBuf>,
+        rules: Option<String>,
+        decoder: Option<String>,
+    },
+    Cipher {
+        input: Option<String>,
+        score: u64,
+    },
+    Analyze {
+        input: Option<String>,
+        scoring: Option<PathBuf>,
+        decoder: String,
+    },
+    Simulate {
+        players: [String; 2],
+        simulation: Simulation,
+        scoring: Option<PathBuf>,
+    },
+    League {
+        guides: Vec<PathBuf>,
+        scoring: Option<PathBuf>,
+    },
+    Breakdown {
+        input: Option<String>,
+        scoring: Option<PathBuf>,
+        decoder: String,
+        csv: bool,
+        summary: bool,
+    },
+}
+
+/// The integer type the calories of day 1 are summed with.
+#[derive(Clone, Copy)]
+enum Width {
+    U32,
+    U64,
+    U128,
+}
+
+impl std::str::FromStr for Width {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "u32" => Ok(Width::U32),
+            "u64" => Ok(Width::U64),
+            "u128" => Ok(Width::U128),
+            _ => Err(format!("unknown width '{s}', expected u32, u64 or u128")),
+        }
+    }
+}
+
+impl Command {
+    fn from_args(args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut args = args.peekable();
+        match args.peek().map(String::as_str) {
+            None => Ok(Command::All {
+                format: Format::default(),
+            }),
+            Some("all") => {
+                args.next();
+                Command::all_from_args(args)
+            }
+            Some("bench") => {
+                args.next();
+                Command::bench_from_args(args)
+            }
+            Some("verify") => {
+                args.next();
+                Command::verify_from_args(args)
+            }
+            Some("stats") => {
+                args.next();
+                Command::stats_from_args(args)
+            }
+            Some("plan") => {
+                args.next();
+                Command::plan_from_args(args)
+            }
+            Some("top") => {
+                args.next();
+                Command::top_from_args(args)
+            }
+            Some("score") => {
+                args.next();
+                Command::score_from_args(args)
+            }
+            Some("cipher") => {
+                args.next();
+                Command::cipher_from_args(args)
+            }
+            Some("analyze") => {
+                args.next();
+                Command::analyze_from_args(args)
+            }
+            Some("simulate") => {
+                args.next();
+                Command::simulate_from_args(args)
+            }
+            Some("breakdown") => {
+                args.next();
+                Command::breakdown_from_args(args)
+            }
+            Some("league") => {
+                args.next();
+                Command::league_from_args(args)
+            }
+            Some(_) => Command::day_from_args(args),
+        }
+    }
+
+    fn all_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut format = Format::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" | "-f" => format = parse_value(&arg, args.next())?,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(Command::All { format })
+    }
+
+    fn day_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut day = None;
+        let mut part = None;
+        let mut input = None;
+        let mut format = Format::default();
+        let mut stream = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" | "-d" => day = Some(parse_value(&arg, args.next())?),
+                "--part" | "-p" => part = Some(parse_value(&arg, args.next())?),
+                "--input" | "-i" => input = Some(parse_value(&arg, args.next())?),
+                "--format" | "-f" => format = parse_value(&arg, args.next())?,
+                "--stream" | "-s" => stream = true,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        if matches!(part, Some(part) if part != 1 && part != 2) {
+            return Err("--part must be 1 or 2".to_owned());
+        }
+
+        let day = day.ok_or("missing --day")?;
+        Ok(Command::Day {
+            puzzle: find_puzzle(day)?,
+            part,
+            input,
+            format,
+            stream,
+        })
+    }
+
+    fn bench_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut puzzles = aoc2022::puzzles().to_vec();
+        let mut config = BenchConfig::default();
+        let mut baseline = PathBuf::from(DEFAULT_BASELINE);
+        let mut threshold: f64 = 10.0;
+        let mut save_baseline = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" | "-d" => puzzles = vec![find_puzzle(parse_value(&arg, args.next())?)?],
+                "--iterations" | "-n" => config.iterations = parse_value(&arg, args.next())?,
+                "--warmup" | "-w" => config.warmup = parse_value(&arg, args.next())?,
+                "--baseline" | "-b" => baseline = parse_value(&arg, args.next())?,
+                "--threshold" | "-t" => threshold = parse_value(&arg, args.next())?,
+                "--save-baseline" => save_baseline = true,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        if config.iterations == 0 {
+            return Err("--iterations must be at least 1".to_owned());
+        }
+        if !(threshold.is_finite() && threshold >= 0.0) {
+            return Err(format!(
+                "--threshold must be a percentage of 0 or more, not {threshold}"
+            ));
+        }
+
+        Ok(Command::Bench {
+            puzzles,
+            config,
+            baseline,
+            threshold: threshold / 100.0,
+            save_baseline,
+        })
+    }
+
+    fn verify_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut puzzles = aoc2022::puzzles().to_vec();
+        let mut answers = KnownAnswers::default_path();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" | "-d" => puzzles = vec![find_puzzle(parse_value(&arg, args.next())?)?],
+                "--answers" | "-a" => answers = parse_value(&arg, args.next())?,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(Command::Verify { puzzles, answers })
+    }
+
+    fn stats_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut input = None;
+        let mut bins = DEFAULT_BINS;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => input = Some(parse_value(&arg, args.next())?),
+                "--bins" => bins = parse_value(&arg, args.next())?,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        if bins == 0 {
+            return Err("--bins must be at least 1".to_owned());
+        }
+
+        Ok(Command::Stats { input, bins })
+    }
+
+    fn plan_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut input = None;
+        let mut elves = None;
+        let mut strategy = Strategy::default();
+        let mut show_items = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => input = Some(parse_value(&arg, args.next())?),
+                "--elves" | "-k" => elves = Some(parse_value(&arg, args.next())?),
+                "--strategy" | "-s" => strategy = parse_value(&arg, args.next())?,
+                "--items" => show_items = true,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        let elves = elves.ok_or("missing --elves")?;
+        if elves == 0 {
+            return Err("--elves must be at least 1".to_owned());
+        }
+
+        Ok(Command::Plan {
+            input,
+            elves,
+            strategy,
+            show_items,
+        })
+    }
+
+    fn top_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut input = None;
+        let mut n = 3;
+        let mut width = Width::U64;
+        let mut threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => input = Some(parse_value(&arg, args.next())?),
+                "-n" => n = parse_value(&arg, args.next())?,
+                "--threads" | "-t" => threads = parse_value(&arg, args.next())?,
+                "--width" => width = parse_value(&arg, args.next())?,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        if threads == 0 {
+            return Err("--threads must be at least 1".to_owned());
+        }
+
+        Ok(Command::Top {
+            input,
+            n,
+            threads,
+            width,
+        })
+    }
+
+    fn score_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut input = None;
+        let mut scoring = None;
+        let mut rules = None;
+        let mut decoder = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => input = Some(parse_value(&arg, args.next())?),
+                "--scoring" => scoring = Some(parse_value(&arg, args.next())?),
+                "--rules" => rules = Some(parse_value(&arg, args.next())?),
+                "--decoder" => decoder = Some(parse_value(&arg, args.next())?),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        if scoring.is_some() && rules.is_some() {
+            return Err("--scoring and --rules cannot be used together".to_owned());
+        }
+
+        Ok(Command::Score {
+            input,
+            scoring,
+            rules,
+            decoder,
+        })
+    }
+
+    fn cipher_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut input = None;
+        let mut score = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => input = Some(parse_value(&arg, args.next())?),
+                "--score" => score = Some(parse_value(&arg, args.next())?),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        let score = score.ok_or("missing --score")?;
+        Ok(Command::Cipher { input, score })
+    }
+
+    fn analyze_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut input = None;
+        let mut scoring = None;
+        let mut decoder = "shape".to_owned();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => input = Some(parse_value(&arg, args.next())?),
+                "--scoring" => scoring = Some(parse_value(&arg, args.next())?),
+                "--decoder" => decoder = parse_value(&arg, args.next())?,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(Command::Analyze {
+            input,
+            scoring,
+            decoder,
+        })
+    }
+
+    fn simulate_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut players = vec![];
+        let mut simulation = DEFAULT_SIMULATION;
+        let mut scoring = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--matches" => simulation.matches = parse_value(&arg, args.next())?,
+                "--rounds" | "-n" => simulation.rounds = parse_value(&arg, args.next())?,
+                "--seed" => simulation.seed = parse_value(&arg, args.next())?,
+                "--scoring" => scoring = Some(parse_value(&arg, args.next())?),
+                _ if !arg.starts_with('-') => players.push(arg),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        let players: [String; 2] = players
+            .try_into()
+            .map_err(|_| "expected two players".to_owned())?;
+        Ok(Command::Simulate {
+            players,
+            simulation,
+            scoring,
+        })
+    }
+
+    fn league_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut guides = vec![];
+        let mut scoring = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--scoring" => scoring = Some(parse_value(&arg, args.next())?),
+                _ if !arg.starts_with('-') => guides.push(PathBuf::from(arg)),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        if guides.len() < 2 {
+            return Err("a league needs at least two guides".to_owned());
+        }
+
+        Ok(Command::League { guides, scoring })
+    }
+
+    fn breakdown_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let mut input = None;
+        let mut scoring = None;
+        let mut decoder = "shape".to_owned();
+        let mut csv = false;
+        let mut summary = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => input = Some(parse_value(&arg, args.next())?),
+                "--scoring" => scoring = Some(parse_value(&arg, args.next())?),
+                "--decoder" => decoder = parse_value(&arg, args.next())?,
+                "--csv" => csv = true,
+                "--summary" => summary = true,
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(Command::Breakdown {
+            input,
+            scoring,
+            decoder,
+            csv,
+            summary,
+        })
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or(format!("missing value for {flag}"))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}' for {flag}"))
+}
+
+fn find_puzzle(day: u8) -> Result<&'static dyn Puzzle, String> {
+    aoc2022::puzzle(day).ok_or(format!("day {day} is not solved yet"))
+}
+
+/// Solves the requested parts of a day from its input text.
+fn solve(puzzle: &dyn Puzzle, parts: &[u8], input: &str) -> aoc2022::Result<Vec<PartAnswer>> {
+    solution::solve(puzzle, &mut input.as_bytes(), parts)
+}
+
+fn run_day(
+    puzzle: &dyn Puzzle,
+    part: Option<u8>,
+    input: Option<&str>,
+    format: Format,
+    stream: bool,
+) -> Result<(), Box<dyn Error>> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let mut reader = InputSource::resolve(puzzle.day(), input).open()?;
+    let answers = if stream {
+        solution::stream(puzzle, &mut reader, &parts)?
+    } else {
+        solution::solve(puzzle, &mut reader, &parts)?
+    };
+    output::print_answers(&answers, format);
+
+    Ok(())
+}
+
+fn run_all(format: Format) -> Result<(), Box<dyn Error>> {
+    let mut rows = vec![];
+    for puzzle in aoc2022::puzzles() {
+        let input = InputSource::resolve(puzzle.day(), None).read_to_string()?;
+        let answers = solve(*puzzle, &[1, 2], &input)?;
+        if format == Format::Json {
+            output::print_answers(&answers, format);
+        }
+        rows.push((
+            puzzle.day(),
+            answers[0].answer.clone(),
+            answers[1].answer.clone(),
+        ));
+    }
+
+    if format == Format::Json {
+        return Ok(());
+    }
+
+    let width1 = rows.iter().map(|row| row.1.len()).max().unwrap_or(0).max(6);
+    let width2 = rows.iter().map(|row| row.2.len()).max().unwrap_or(0).max(6);
+
+    println!("Day | {:<width1$} | Part 2", "Part 1");
+    println!("----+-{}-+-{}", "-".repeat(width1), "-".repeat(width2));
+    for (day, part1, part2) in rows {
+        println!("{day:>3} | {part1:<width1$} | {part2}");
+    }
+
+    Ok(())
+}
+
+/// Benchmarks the puzzles and returns whether any of them got slower than the baseline.
+fn run_bench(
+    puzzles: &[&dyn Puzzle],
+    config: &BenchConfig,
+    baseline_path: &Path,
+    threshold: f64,
+    save_baseline: bool,
+) -> Result<bool, Box<dyn Error>> {
+    let baseline = Baseline::load(baseline_path)?;
+    let save_baseline = save_baseline || baseline.is_none();
+    let mut baseline = baseline.unwrap_or_default();
+    let mut regressed = false;
+
+    println!(
+        "Day | Phase | {:>10} | {:>10} | {:>10} | Baseline",
+        "min", "median", "p95"
+    );
+    println!("----+-------+------------+------------+------------+---------");
+    for puzzle in puzzles {
+        let input = InputSource::resolve(puzzle.day(), None).read_to_string()?;
+        let timings = bench::bench(*puzzle, &input, config)?;
+        let regressions = baseline.regressions(&timings, threshold);
+
+        for (phase, phase_timings) in timings.phases() {
+            let status = match regressions.iter().find(|r| r.phase == phase) {
+                Some(regression) => format!(
+                    "REGRESSION (was {:.1?})",
+                    Duration::from_nanos(regression.baseline_ns)
+                ),
+                None => String::new(),
+            };
+            let row = format!(
+                "{:>3} | {phase:<5} | {:>10.1?} | {:>10.1?} | {:>10.1?} | {status}",
+                timings.day,
+                Duration::from_nanos(phase_timings.min_ns),
+                Duration::from_nanos(phase_timings.median_ns),
+                Duration::from_nanos(phase_timings.p95_ns),
+            );
+            println!("{}", row.trim_end());
+        }
+
+        regressed |= !regressions.is_empty();
+        if save_baseline {
+            baseline.update(timings);
+        }
+    }
+
+    if save_baseline {
+        baseline.save(baseline_path)?;
+        println!("Saved baseline to {}", baseline_path.display());
+    }
+
+    Ok(regressed)
+}
+
+/// Checks every answer against the registry and returns whether any of them is wrong.
+fn run_verify(puzzles: &[&dyn Puzzle], answers_path: &Path) -> Result<bool, Box<dyn Error>> {
+    let answers = KnownAnswers::load(answers_path)?;
+    let mut failed = false;
+
+    println!("Day | Part | {:<12} | Status", "Answer");
+    println!("----+------+--------------+-------");
+    for puzzle in puzzles {
+        let input = InputSource::resolve(puzzle.day(), None).read_to_string()?;
+        let solved = solve(*puzzle, &[1, 2], &input)?;
+
+        for PartAnswer { part, answer, .. } in solved {
+            let verdict = Verdict::check(answers.expected(puzzle.day(), part), &answer);
+            failed |= matches!(verdict, Verdict::Fail { .. });
+            println!("{:>3} | {part:>4} | {answer:<12} | {verdict}", puzzle.day());
+        }
+    }
+
+    Ok(failed)
+}
+
+fn run_stats(input: Option<&str>, bins: usize) -> Result<(), Box<dyn Error>> {
+    let reader = InputSource::resolve(1, input).open()?;
+    let inventory = Inventory::<u64>::from_reader(reader)?;
+    let stats = Statistics::new(&inventory, bins).ok_or("the input has no elves")?;
+    print!("{stats}");
+
+    Ok(())
+}
+
+fn run_plan(
+    input: Option<&str>,
+    elves: usize,
+    strategy: Strategy,
+    show_items: bool,
+) -> Result<(), Box<dyn Error>> {
+    let reader = InputSource::resolve(1, input).open()?;
+    let inventory = Inventory::<u64>::from_reader(reader)?;
+    let plan = Plan::new(&Item::all(&inventory), elves, strategy);
+    if show_items {
+        print!("{plan:#}");
+    } else {
+        print!("{plan}");
+    }
+
+    Ok(())
+}
+
+fn run_top(
+    input: Option<&str>,
+    n: usize,
+    threads: usize,
+    width: Width,
+) -> Result<(), Box<dyn Error>> {
+    let InputSource::File(path) = InputSource::resolve(1, input) else {
+        return Err("`top` reads the input on several threads, so it needs a file".into());
+    };
+    match width {
+        Width::U32 => print_top(parallel::top_n::<u32>(&path, n, threads)?),
+        Width::U64 => print_top(parallel::top_n::<u64>(&path, n, threads)?),
+        Width::U128 => print_top(parallel::top_n::<u128>(&path, n, threads)?),
+    }
+
+    Ok(())
+}
+
+fn print_top<T: Calories>(mut top: Vec<(usize, T)>) {
+    top.sort_by_key(|&(elf, total)| (std::cmp::Reverse(total), elf));
+
+    println!("Rank | {:>6} | Calories", "Elf");
+    println!("-----+--------+---------");
+    for (rank, (elf, total)) in top.iter().enumerate() {
+        println!("{:>4} | {elf:>6} | {total}", rank + 1);
+    }
+    let total: u128 = top.iter().map(|&(_, total)| total.into()).sum();
+    println!("Total: {total}");
+}
+
+fn load_scoring(path: Option<&Path>) -> aoc2022::Result<Scoring> {
+    match path {
+        Some(path) => Scoring::load(path),
+        None => Ok(Scoring::classic().clone()),
+    }
+}
+
+fn find_decoder<'a>(decoders: &'a Decoders, name: &str) -> Result<&'a dyn GuideDecoder, String> {
+    decoders.get(name).ok_or_else(|| {
+        format!(
+            "unknown decoder '{name}', expected one of: {}",
+            decoders.names().join(", ")
+        )
+    })
+}
+
+/// Scores a guide with the rules called `name`, which only decode the `shape` and
+/// `outcome` readings of the second column.
+fn run_score_with_rules(
+    input: Option<&str>,
+    name: &str,
+    decoder: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let rules = Rules::by_name(name)
+        .ok_or_else(|| format!("unknown rules '{name}', expected one of: classic, rpsls"))?;
+    let guide = InputSource::resolve(2, input).read_to_string()?;
+
+    let readings = match decoder {
+        None => vec![("Part 1", Column::Shape), ("Part 2", Column::Outcome)],
+        Some("shape") => vec![("shape", Column::Shape)],
+        Some("outcome") => vec![("outcome", Column::Outcome)],
+        Some(name) => {
+            return Err(format!("unknown decoder '{name}', expected one of: shape, outcome").into())
+        }
+    };
+    for (label, column) in readings {
+        println!("{label}: {}", rules.score_guide(guide.as_bytes(), column)?);
+    }
+
+    Ok(())
+}
+
+fn run_score(
+    input: Option<&str>,
+    scoring: Option<&Path>,
+    decoder: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let scoring = load_scoring(scoring)?;
+    let guide = InputSource::resolve(2, input).read_to_string()?;
+
+    if let Some(name) = decoder {
+        let decoders = Decoders::default();
+        let decoder = find_decoder(&decoders, name)?;
+        let tournament = Tournament::decode(guide.as_bytes(), decoder, &scoring)?;
+        println!("{name}: {}", tournament.score_with(&scoring));
+        return Ok(());
+    }
+
+    let part1 = Tournament::from_reader_with(guide.as_bytes(), &scoring)?;
+    let part2 = Tournament::from_reader_part2_with(guide.as_bytes(), &scoring)?;
+    println!("Part 1: {}", part1.score_with(&scoring));
+    println!("Part 2: {}", part2.score_with(&scoring));
+
+    Ok(())
+}
+
+fn run_cipher(input: Option<&str>, score: u64) -> Result<(), Box<dyn Error>> {
+    let reader = InputSource::resolve(2, input).open()?;
+    let guide = EncodedGuide::from_reader(reader)?;
+    let ciphers = guide.deduce(score);
+    if ciphers.is_empty() {
+        return Err(format!("no cipher gives {score} points").into());
+    }
+
+    for cipher in ciphers {
+        println!("{cipher}");
+    }
+
+    Ok(())
+}
+
+fn run_analyze(
+    input: Option<&str>,
+    scoring: Option<&Path>,
+    decoder: &str,
+) -> Result<(), Box<dyn Error>> {
+    let scoring = load_scoring(scoring)?;
+    let decoders = Decoders::default();
+    let decoder = find_decoder(&decoders, decoder)?;
+    let reader = InputSource::resolve(2, input).open()?;
+    let tournament = Tournament::decode(reader, decoder, &scoring)?;
+    print!("{}", Analysis::new(&tournament, &scoring));
+
+    Ok(())
+}
+
+fn run_simulate(
+    players: &[String; 2],
+    simulation: &Simulation,
+    scoring: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let scoring = load_scoring(scoring)?;
+    let first = PlayerSpec::parse(&players[0])?;
+    let second = PlayerSpec::parse(&players[1])?;
+    print!("{}", simulation.run(&first, &second, &scoring));
+
+    Ok(())
+}
+
+fn run_breakdown(
+    input: Option<&str>,
+    scoring: Option<&Path>,
+    decoder: &str,
+    csv: bool,
+    summary: bool,
+) -> Result<(), Box<dyn Error>> {
+    let scoring = load_scoring(scoring)?;
+    let decoders = Decoders::default();
+    let decoder = find_decoder(&decoders, decoder)?;
+    let reader = InputSource::resolve(2, input).open()?;
+    let tournament = Tournament::decode(reader, decoder, &scoring)?;
+    let breakdown = Breakdown::new(&tournament, &scoring);
+
+    match (csv, summary) {
+        (true, true) => print!("{}", breakdown.shapes_csv()),
+        (true, false) => print!("{}", breakdown.rounds_csv()),
+        (false, true) => print!("{}", breakdown.shapes_table()),
+        (false, false) => print!("{breakdown}"),
+    }
+
+    Ok(())
+}
+
+fn run_league(guides: &[PathBuf], scoring: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let scoring = load_scoring(scoring)?;
+    print!("{}", League::load(guides, &scoring)?);
+
+    Ok(())
+}
+
+fn main() {
+    let (level, args) = log::take_verbosity(env::args().skip(1));
+    log::set_level(level);
+
+    let command = match Command::from_args(args.into_iter()) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let result = match command {
+        Command::All { format } => run_all(format),
+        Command::Day {
+            puzzle,
+            part,
+            input,
+            format,
+            stream,
+        } => run_day(puzzle, part, input.as_deref(), format, stream),
+        Command::Bench {
+            puzzles,
+            config,
+            baseline,
+            threshold,
+            save_baseline,
+        } => match run_bench(&puzzles, &config, &baseline, threshold, save_baseline) {
+            Ok(true) => Err("some phases are slower than the baseline".into()),
+            Ok(false) => Ok(()),
+            Err(err) => Err(err),
+        },
+        Command::Verify { puzzles, answers } => match run_verify(&puzzles, &answers) {
+            Ok(true) => Err("some answers do not match the registry".into()),
+            Ok(false) => Ok(()),
+            Err(err) => Err(err),
+        },
+        Command::Stats { input, bins } => run_stats(input.as_deref(), bins),
+        Command::Plan {
+            input,
+            elves,
+            strategy,
+            show_items,
+        } => run_plan(input.as_deref(), elves, strategy, show_items),
+        Command::Top {
+            input,
+            n,
+            threads,
+            width,
+        } => run_top(input.as_deref(), n, threads, width),
+        Command::Score {
+            input,
+            scoring,
+            rules,
+            decoder,
+        } => match rules {
+            Some(rules) => run_score_with_rules(input.as_deref(), &rules, decoder.as_deref()),
+            None => run_score(input.as_deref(), scoring.as_deref(), decoder.as_deref()),
+        },
+        Command::Cipher { input, score } => run_cipher(input.as_deref(), score),
+        Command::Analyze {
+            input,
+            scoring,
+            decoder,
+        } => run_analyze(input.as_deref(), scoring.as_deref(), &decoder),
+        Command::Simulate {
+            players,
+            simulation,
+            scoring,
+        } => run_simulate(&players, &simulation, scoring.as_deref()),
+        Command::League { guides, scoring } => run_league(&guides, scoring.as_deref()),
+        Command::Breakdown {
+            input,
+            scoring,
+            decoder,
+            csv,
+            summary,
+        } => run_breakdown(input.as_deref(), scoring.as_deref(), &decoder, csv, summary),
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
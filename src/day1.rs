@@ -0,0 +1,262 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub mod parallel;
+pub mod planner;
+pub mod stats;
+
+use crate::records;
+use crate::{debug, Error, Result, Solution};
+
+/// An unsigned integer type the calories of the elves are read and summed with.
+pub trait Calories:
+    Copy + Ord + Default + FromStr + fmt::Display + fmt::Debug + Send + Into<u128>
+{
+    /// Name of the type, used when a sum overflows.
+    const WIDTH: &'static str;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_calories {
+    ($($width:ty),*) => {
+        $(
+            impl Calories for $width {
+                const WIDTH: &'static str = stringify!($width);
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$width>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_calories!(u32, u64, u128);
+
+/// Calls `f` with the calories of the items carried by each elf and their total, in the
+/// order of the input. A total that does not fit in `T` is reported as an
+/// [`Error::Overflow`].
+fn for_each_elf<T: Calories>(reader: impl BufRead, mut f: impl FnMut(&[T], T)) -> Result<()> {
+    let mut groups = records::groups(reader, |line| {
+        line.parse::<T>()
+            .map_err(|_| Error::parse(1, 1, line, "expected an amount of calories"))
+    });
+
+    let mut elf = 0;
+    while let Some(calories) = groups.next() {
+        let calories = calories?;
+        let mut total = T::default();
+        for (offset, &item) in calories.iter().enumerate() {
+            total = total.checked_add(item).ok_or(Error::Overflow {
+                day: 1,
+                line: groups.group_start() + offset,
+                elf,
+                width: T::WIDTH,
+            })?;
+        }
+
+        f(&calories, total);
+        elf += 1;
+    }
+
+    Ok(())
+}
+
+/// Keeps the `n` elves carrying the most calories seen so far, without storing the others.
+/// When two elves carry the same calories, the first one wins.
+#[derive(Debug)]
+struct TopElves<T> {
+    n: usize,
+    /// A min-heap, so the elf to drop is always at the top.
+    heap: BinaryHeap<Reverse<(T, Reverse<usize>)>>,
+}
+
+impl<T: Calories> TopElves<T> {
+    fn new(n: usize) -> TopElves<T> {
+        TopElves {
+            n,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    fn push(&mut self, elf_index: usize, total: T) {
+        self.heap.push(Reverse((total, Reverse(elf_index))));
+        if self.heap.len() > self.n {
+            self.heap.pop();
+        }
+    }
+
+    /// The `(elf_index, total)` pairs kept, in the order of the input.
+    fn into_vec(self) -> Vec<(usize, T)> {
+        let mut elves: Vec<(usize, T)> = self
+            .heap
+            .into_iter()
+            .map(|Reverse((total, Reverse(elf_index)))| (elf_index, total))
+            .collect();
+        elves.sort();
+        elves
+    }
+}
+
+/// The calories carried by each elf, in the order of the input, summed with `T`.
+#[derive(Debug, PartialEq, Default)]
+pub struct Inventory<T = u32> {
+    totals: Vec<T>,
+    items: Vec<Vec<T>>,
+}
+
+impl<T: Calories> Inventory<T> {
+    pub fn from_reader(reader: impl BufRead) -> Result<Inventory<T>> {
+        let mut totals = vec![];
+        let mut items = vec![];
+        for_each_elf(reader, |calories, total| {
+            totals.push(total);
+            items.push(calories.to_vec());
+        })?;
+
+        debug!("read {} elves", totals.len());
+        Ok(Inventory { totals, items })
+    }
+
+    /// The total calories of each elf, indexed by the position of the elf in the input.
+    pub fn totals(&self) -> &[T] {
+        &self.totals
+    }
+
+    /// The calories of the items carried by each elf, indexed like [`Inventory::totals`].
+    pub fn items(&self) -> &[Vec<T>] {
+        &self.items
+    }
+
+    /// The `(elf_index, total)` pairs of the `n` elves carrying the most calories, in the
+    /// order of the input. Elf indexes start at 0.
+    pub fn top_n(&self, n: usize) -> Vec<(usize, T)> {
+        let mut top = TopElves::new(n);
+        for (elf_index, &total) in self.totals.iter().enumerate() {
+            top.push(elf_index, total);
+        }
+        top.into_vec()
+    }
+}
+
+/// The calories carried by the top elves, which always fit in a `u128` as there are only
+/// a few of them.
+fn top_total<T: Calories>(top: &[(usize, T)]) -> u128 {
+    top.iter().map(|&(_, total)| total.into()).sum()
+}
+
+/// The calories carried by the elf carrying the most.
+fn most<T: Calories>(top: &[(usize, T)]) -> T {
+    top.iter()
+        .map(|&(_, total)| total)
+        .max()
+        .unwrap_or_default()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Inventory<u64>;
+    type Part1 = u64;
+    type Part2 = u128;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        Inventory::from_reader(reader)
+    }
+
+    /// The calories carried by the elf carrying the most.
+    fn part1(input: &Self::Input) -> u64 {
+        let top = input.top_n(1);
+        if let Some((elf_index, total)) = top.first() {
+            debug!("elf {elf_index} carries the most calories: {total}");
+        }
+        most(&top)
+    }
+
+    /// The calories carried by the top three elves.
+    fn part2(input: &Self::Input) -> u128 {
+        top_total(&input.top_n(3))
+    }
+
+    /// Only keeps the top three elves.
+    fn stream(reader: impl BufRead) -> Result<(u64, u128)> {
+        let mut top = TopElves::new(3);
+        let mut elf_index = 0;
+        for_each_elf(reader, |_, total| {
+            top.push(elf_index, total);
+            elf_index += 1;
+        })?;
+
+        let top = top.into_vec();
+        Ok((most(&top), top_total(&top)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day1::{Day1, Inventory};
+    use crate::Solution;
+
+    #[test]
+    fn can_count_calories() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n\n";
+        let elves = Day1::parse(input.as_bytes()).unwrap();
+
+        assert_eq!(24000, Day1::part1(&elves));
+        assert_eq!(45000, Day1::part2(&elves));
+    }
+
+    #[test]
+    fn can_count_the_last_elf() {
+        let input = "1000\r\n\r\n\r\n2000\r\n3000";
+        let elves = Day1::parse(input.as_bytes()).unwrap();
+
+        assert_eq!(&[1000, 5000], elves.totals());
+        assert_eq!((5000, 6000), Day1::stream(input.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn can_find_the_top_elves() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        let inventory = Inventory::<u32>::from_reader(input.as_bytes()).unwrap();
+
+        assert_eq!(vec![(3, 24000)], inventory.top_n(1));
+        assert_eq!(vec![(2, 11000), (3, 24000), (4, 10000)], inventory.top_n(3));
+        assert_eq!(5, inventory.top_n(10).len());
+        assert_eq!(5, inventory.top_n(usize::MAX).len());
+        assert!(inventory.top_n(0).is_empty());
+
+        // the first elf wins a tie.
+        let inventory = Inventory::<u32>::from_reader("5\n\n7\n\n7\n".as_bytes()).unwrap();
+        assert_eq!(vec![(1, 7)], inventory.top_n(1));
+    }
+
+    #[test]
+    fn reports_overflowing_elves() {
+        let input = "1\n\n4294967295\n\n2\n4294967295\n";
+        let err = Inventory::<u32>::from_reader(input.as_bytes()).unwrap_err();
+        assert_eq!(
+            "day 1, line 6: the calories of elf 2 do not fit in a u32",
+            err.to_string()
+        );
+
+        let inventory = Inventory::<u64>::from_reader(input.as_bytes()).unwrap();
+        assert_eq!(&[1, 4294967295, 4294967297], inventory.totals());
+    }
+
+    #[test]
+    fn reports_invalid_calories() {
+        let err = Day1::parse("1000\n\n20x0\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            "day 1, line 3, column 1: expected an amount of calories: '20x0'",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Sums the elves of very large inputs on several threads. The file is split in chunks
+//! that start on blank lines, so no elf is shared by two chunks, and the top elves of
+//! each chunk are merged at the end.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::thread;
+
+use crate::day1::{for_each_elf, most, top_total, Calories, TopElves};
+use crate::{debug, Error, Result};
+
+/// The elves found in a chunk of the input.
+struct Chunk<T> {
+    elves: usize,
+    /// The top elves of the chunk, indexed from the first elf of the chunk.
+    top: Vec<(usize, T)>,
+}
+
+/// The `(elf_index, total)` pairs of the `n` elves carrying the most calories, in the
+/// order of the input, like [`Inventory::top_n`](crate::day1::Inventory::top_n).
+pub fn top_n<T: Calories>(path: &Path, n: usize, threads: usize) -> Result<Vec<(usize, T)>> {
+    let file = File::open(path).map_err(|source| Error::Input {
+        path: path.to_owned(),
+        source,
+    })?;
+    let boundaries = chunk_boundaries(file, threads.max(1))?;
+    debug!("split {} in {:?}", path.display(), boundaries);
+
+    let chunks: Vec<Result<Chunk<T>>> = thread::scope(|scope| {
+        let workers: Vec<_> = boundaries
+            .windows(2)
+            .map(|range| {
+                let (start, end) = (range[0], range[1]);
+                scope.spawn(move || sum_chunk(path, start, end, n))
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("summing a chunk does not panic"))
+            .collect()
+    });
+
+    let mut top = TopElves::new(n);
+    let mut first_elf = 0;
+    for (chunk, &start) in chunks.into_iter().zip(&boundaries) {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => return Err(locate(err, count_lines(path, start)?, first_elf)),
+        };
+        for (elf_index, total) in chunk.top {
+            top.push(first_elf + elf_index, total);
+        }
+        first_elf += chunk.elves;
+    }
+
+    Ok(top.into_vec())
+}
+
+/// Both answers of day 1, computed from a file on `threads` threads.
+pub fn solve<T: Calories>(path: &Path, threads: usize) -> Result<(T, u128)> {
+    let top = top_n::<T>(path, 3, threads)?;
+    Ok((most(&top), top_total(&top)))
+}
+
+/// Moves an error found in a chunk to the line and elf of the whole input.
+fn locate(err: Error, lines: usize, first_elf: usize) -> Error {
+    match err.after_lines(lines) {
+        Error::Overflow {
+            day,
+            line,
+            elf,
+            width,
+        } => Error::Overflow {
+            day,
+            line,
+            elf: first_elf + elf,
+            width,
+        },
+        err => err,
+    }
+}
+
+/// Splits a file in at most `chunks` byte ranges, returned as their boundaries from 0 to
+/// the length of the file. Every range but the first starts on a blank line.
+fn chunk_boundaries(file: File, chunks: usize) -> Result<Vec<u64>> {
+    let len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+
+    let mut boundaries = vec![0];
+    for chunk in 1..chunks as u64 {
+        let start = (len * chunk / chunks as u64).max(*boundaries.last().unwrap());
+        let boundary = next_blank_line(&mut reader, start)?;
+        if boundary >= len {
+            break;
+        }
+        if boundary > *boundaries.last().unwrap() {
+            boundaries.push(boundary);
+        }
+    }
+    boundaries.push(len);
+
+    Ok(boundaries)
+}
+
+/// The offset of the first blank line starting after `offset`, or the end of the file.
+fn next_blank_line(reader: &mut BufReader<File>, offset: u64) -> Result<u64> {
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut line = vec![];
+
+    // the line holding the offset may have started before it.
+    let mut position = offset + reader.read_until(b'\n', &mut line)? as u64;
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 || line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(position);
+        }
+        position += read as u64;
+    }
+}
+
+fn sum_chunk<T: Calories>(path: &Path, start: u64, end: u64, n: usize) -> Result<Chunk<T>> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    let reader = BufReader::new(file.take(end - start));
+
+    let mut top = TopElves::new(n);
+    let mut elves = 0;
+    for_each_elf(reader, |_, total| {
+        top.push(elves, total);
+        elves += 1;
+    })?;
+
+    Ok(Chunk {
+        elves,
+        top: top.into_vec(),
+    })
+}
+
+/// The number of lines before `offset`, which starts a line.
+fn count_lines(path: &Path, offset: u64) -> Result<usize> {
+    let reader = BufReader::new(File::open(path)?.take(offset));
+    let mut lines = 0;
+    for byte in reader.bytes() {
+        lines += usize::from(byte? == b'\n');
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::day1::{parallel, Day1, Inventory};
+    use crate::Solution;
+
+    /// Writes an input to a file of the temporary directory, named after the test.
+    fn input_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("aoc2022-day1-{name}"));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn can_sum_elves_in_parallel() {
+        let mut input = String::new();
+        for elf in 0..200u32 {
+            for item in 0..elf % 5 + 1 {
+                input.push_str(&format!("{}\r\n", (elf * 7919 + item * 104729) % 10000));
+            }
+            input.push_str(if elf % 3 == 0 { "\r\n\r\n" } else { "\r\n" });
+        }
+        let path = input_file("parallel", &input);
+
+        let inventory = Inventory::<u64>::from_reader(input.as_bytes()).unwrap();
+        let expected = Day1::stream(input.as_bytes()).unwrap();
+        for threads in [1, 2, 3, 8, 1000] {
+            assert_eq!(
+                inventory.top_n(5),
+                parallel::top_n(&path, 5, threads).unwrap()
+            );
+            assert_eq!(expected, parallel::solve::<u64>(&path, threads).unwrap());
+        }
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reports_invalid_calories_on_their_line() {
+        let input = "1\n\n".repeat(50) + "2x\n\n" + &"3\n\n".repeat(50);
+        let path = input_file("parallel-invalid", &input);
+
+        let err = parallel::solve::<u32>(&path, 4).unwrap_err();
+        assert_eq!(
+            "day 1, line 101, column 1: expected an amount of calories: '2x'",
+            err.to_string()
+        );
+
+        let input = "1\n\n".repeat(50) + "4294967295\n1\n\n" + &"3\n\n".repeat(50);
+        fs::write(&path, input).unwrap();
+        let err = parallel::solve::<u32>(&path, 4).unwrap_err();
+        assert_eq!(
+            "day 1, line 102: the calories of elf 50 do not fit in a u32",
+            err.to_string()
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,416 @@
+//! Redistributes the items of the elves between `k` elves, so the heaviest load is as
+//! light as possible (multiway number partitioning).
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::day1::Inventory;
+use crate::debug;
+
+/// Inputs with at most this many items are solved exactly by [`Strategy::Auto`].
+pub const EXACT_LIMIT: usize = 20;
+
+/// An item of the inventory: the elf carrying it, its position in the list of the elf
+/// and its calories. Both indexes start at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item {
+    pub elf: usize,
+    pub index: usize,
+    pub calories: u64,
+}
+
+impl Item {
+    /// Every item of the inventory, in the order of the input.
+    pub fn all(inventory: &Inventory<u64>) -> Vec<Item> {
+        let mut items = vec![];
+        for (elf, calories) in inventory.items().iter().enumerate() {
+            for (index, &calories) in calories.iter().enumerate() {
+                items.push(Item {
+                    elf,
+                    index,
+                    calories,
+                });
+            }
+        }
+        items
+    }
+}
+
+/// How the items are assigned to the elves.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Strategy {
+    /// Exact for small inputs, the best of both heuristics otherwise.
+    #[default]
+    Auto,
+    /// Branch and bound, which is exponential in the number of items.
+    Exact,
+    /// Longest processing time first: the largest item goes to the lightest elf.
+    Lpt,
+    /// Karmarkar–Karp's largest differencing method, extended to `k` elves.
+    KarmarkarKarp,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Strategy::Auto),
+            "exact" => Ok(Strategy::Exact),
+            "lpt" => Ok(Strategy::Lpt),
+            "kk" => Ok(Strategy::KarmarkarKarp),
+            _ => Err(format!(
+                "unknown strategy '{s}', expected auto, exact, lpt or kk"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Auto => write!(f, "auto"),
+            Strategy::Exact => write!(f, "exact"),
+            Strategy::Lpt => write!(f, "lpt"),
+            Strategy::KarmarkarKarp => write!(f, "kk"),
+        }
+    }
+}
+
+/// The items given to each of the `k` elves. The alternate format (`{:#}`) also lists
+/// the items of each elf, as `elf.index=calories`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    /// The strategy that found the plan, never [`Strategy::Auto`].
+    pub strategy: Strategy,
+    pub elves: Vec<Vec<Item>>,
+    /// No plan can have a heaviest load below this.
+    pub lower_bound: u128,
+}
+
+impl Plan {
+    /// Redistributes `items` between `k` elves, which must be at least 1.
+    pub fn new(items: &[Item], k: usize, strategy: Strategy) -> Plan {
+        assert!(k > 0, "items can only be given to at least one elf");
+
+        let mut items = items.to_vec();
+        items.sort_by_key(|item| Reverse(item.calories));
+
+        let plan = match strategy {
+            Strategy::Auto if items.len() <= EXACT_LIMIT => exact(&items, k),
+            Strategy::Auto => {
+                let lpt = lpt(&items, k);
+                let kk = karmarkar_karp(&items, k);
+                if kk.max_load() < lpt.max_load() {
+                    kk
+                } else {
+                    lpt
+                }
+            }
+            Strategy::Exact => exact(&items, k),
+            Strategy::Lpt => lpt(&items, k),
+            Strategy::KarmarkarKarp => karmarkar_karp(&items, k),
+        };
+        debug!(
+            "{} plan for {} items and {k} elves: {}",
+            plan.strategy,
+            items.len(),
+            plan.max_load()
+        );
+        plan
+    }
+
+    fn from_elves(strategy: Strategy, elves: Vec<Vec<Item>>, items: &[Item]) -> Plan {
+        Plan {
+            strategy,
+            lower_bound: lower_bound(items, elves.len()),
+            elves,
+        }
+    }
+
+    /// The calories carried by each elf.
+    pub fn loads(&self) -> Vec<u128> {
+        self.elves.iter().map(|items| load(items)).collect()
+    }
+
+    /// The calories carried by the heaviest elf.
+    pub fn max_load(&self) -> u128 {
+        self.loads().into_iter().max().unwrap_or(0)
+    }
+
+    /// How much heavier the heaviest load is than the lower bound, where 0.1 is 10%.
+    pub fn gap(&self) -> f64 {
+        match self.lower_bound {
+            0 => 0.0,
+            bound => (self.max_load() - bound) as f64 / bound as f64,
+        }
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elf | Items | Calories")?;
+        writeln!(f, "----+-------+---------")?;
+        for (elf, (items, load)) in self.elves.iter().zip(self.loads()).enumerate() {
+            writeln!(f, "{elf:>3} | {:>5} | {load}", items.len())?;
+        }
+        if f.alternate() {
+            writeln!(f)?;
+            for (elf, items) in self.elves.iter().enumerate() {
+                let items = items
+                    .iter()
+                    .map(|item| format!("{}.{}={}", item.elf, item.index, item.calories));
+                writeln!(f, "{elf:>3}: {}", items.collect::<Vec<_>>().join(" "))?;
+            }
+        }
+        writeln!(f)?;
+        writeln!(f, "Strategy:     {}", self.strategy)?;
+        writeln!(f, "Heaviest:     {}", self.max_load())?;
+        writeln!(f, "Lower bound:  {}", self.lower_bound)?;
+        writeln!(f, "Gap:          {:.2}%", self.gap() * 100.0)
+    }
+}
+
+fn load(items: &[Item]) -> u128 {
+    items.iter().map(|item| u128::from(item.calories)).sum()
+}
+
+/// The heaviest load is at least an even share of the calories, and at least the
+/// largest item.
+fn lower_bound(items: &[Item], k: usize) -> u128 {
+    let largest = items.iter().map(|item| u128::from(item.calories)).max();
+    load(items).div_ceil(k as u128).max(largest.unwrap_or(0))
+}
+
+/// Gives each item, from the largest to the smallest, to the lightest elf.
+fn lpt(items: &[Item], k: usize) -> Plan {
+    let mut elves = vec![vec![]; k];
+    let mut loads: BinaryHeap<Reverse<(u128, usize)>> =
+        (0..k).map(|elf| Reverse((0, elf))).collect();
+    for &item in items {
+        let Reverse((load, elf)) = loads.pop().unwrap();
+        elves[elf].push(item);
+        loads.push(Reverse((load + u128::from(item.calories), elf)));
+    }
+
+    Plan::from_elves(Strategy::Lpt, elves, items)
+}
+
+/// A partial plan of the differencing method, with its elves sorted from the heaviest.
+/// Partitions are ordered by their spread.
+#[derive(Debug)]
+struct Partition {
+    elves: Vec<(u128, Vec<Item>)>,
+}
+
+impl Partition {
+    fn spread(&self) -> u128 {
+        self.elves[0].0 - self.elves[self.elves.len() - 1].0
+    }
+
+    /// Merges two partitions, giving the heaviest elves of one the lightest of the other.
+    fn merge(self, other: Partition) -> Partition {
+        let mut elves: Vec<(u128, Vec<Item>)> = self
+            .elves
+            .into_iter()
+            .zip(other.elves.into_iter().rev())
+            .map(|((load1, mut items1), (load2, items2))| {
+                items1.extend(items2);
+                (load1 + load2, items1)
+            })
+            .collect();
+        elves.sort_by_key(|(load, _)| Reverse(*load));
+        Partition { elves }
+    }
+}
+
+impl PartialEq for Partition {
+    fn eq(&self, other: &Self) -> bool {
+        self.spread() == other.spread()
+    }
+}
+
+impl Eq for Partition {}
+
+impl Ord for Partition {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.spread().cmp(&other.spread())
+    }
+}
+
+impl PartialOrd for Partition {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Repeatedly merges the two partitions with the largest spread, starting from a
+/// partition per item that gives the item to a single elf.
+fn karmarkar_karp(items: &[Item], k: usize) -> Plan {
+    let mut partitions: BinaryHeap<Partition> = items
+        .iter()
+        .map(|&item| {
+            let mut elves = vec![(u128::from(item.calories), vec![item])];
+            elves.resize_with(k, || (0, vec![]));
+            Partition { elves }
+        })
+        .collect();
+
+    while partitions.len() > 1 {
+        let first = partitions.pop().unwrap();
+        let second = partitions.pop().unwrap();
+        partitions.push(first.merge(second));
+    }
+
+    let elves = match partitions.pop() {
+        Some(partition) => partition
+            .elves
+            .into_iter()
+            .map(|(_, items)| items)
+            .collect(),
+        None => vec![vec![]; k],
+    };
+    Plan::from_elves(Strategy::KarmarkarKarp, elves, items)
+}
+
+/// Branch and bound over the items sorted from the largest, starting from the plan of
+/// [`lpt`] and stopping early when a plan reaches the lower bound.
+fn exact(items: &[Item], k: usize) -> Plan {
+    struct Search<'a> {
+        items: &'a [Item],
+        lower_bound: u128,
+        loads: Vec<u128>,
+        assignment: Vec<usize>,
+        best_load: u128,
+        best: Vec<usize>,
+    }
+
+    impl Search<'_> {
+        fn visit(&mut self, next: usize, max_load: u128) {
+            if self.best_load == self.lower_bound {
+                return;
+            }
+            if next == self.items.len() {
+                self.best_load = max_load;
+                self.best = self.assignment.clone();
+                return;
+            }
+
+            let calories = u128::from(self.items[next].calories);
+            for elf in 0..self.loads.len() {
+                // elves with the same load are interchangeable, so only the first is tried.
+                if self.loads[..elf].contains(&self.loads[elf]) {
+                    continue;
+                }
+                let load = self.loads[elf] + calories;
+                if load >= self.best_load {
+                    continue;
+                }
+
+                self.loads[elf] = load;
+                self.assignment[next] = elf;
+                self.visit(next + 1, max_load.max(load));
+                self.loads[elf] -= calories;
+            }
+        }
+    }
+
+    let initial = lpt(items, k);
+    let mut search = Search {
+        items,
+        lower_bound: initial.lower_bound,
+        loads: vec![0; k],
+        assignment: vec![0; items.len()],
+        best_load: initial.max_load(),
+        best: vec![],
+    };
+    search.visit(0, 0);
+
+    if search.best.is_empty() && !items.is_empty() {
+        // nothing beats the heuristic, which is optimal then.
+        return Plan {
+            strategy: Strategy::Exact,
+            ..initial
+        };
+    }
+
+    let mut elves = vec![vec![]; k];
+    for (&item, &elf) in items.iter().zip(&search.best) {
+        elves[elf].push(item);
+    }
+    Plan::from_elves(Strategy::Exact, elves, items)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day1::planner::{Item, Plan, Strategy};
+    use crate::day1::Inventory;
+
+    fn items(calories: &[u64]) -> Vec<Item> {
+        calories
+            .iter()
+            .enumerate()
+            .map(|(elf, &calories)| Item {
+                elf,
+                index: 0,
+                calories,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn can_plan_exactly() {
+        // LPT ends with 3+2+2 and 3+2, while 3+3 and 2+2+2 is a perfect split.
+        let items = items(&[3, 3, 2, 2, 2]);
+        let lpt = Plan::new(&items, 2, Strategy::Lpt);
+        assert_eq!(7, lpt.max_load());
+        assert!((lpt.gap() - 1.0 / 6.0).abs() < 1e-9);
+
+        let plan = Plan::new(&items, 2, Strategy::Exact);
+        assert_eq!(6, plan.max_load());
+        assert_eq!(6, plan.lower_bound);
+        assert_eq!(0.0, plan.gap());
+        assert_eq!(5, plan.elves.iter().map(Vec::len).sum::<usize>());
+    }
+
+    #[test]
+    fn can_plan_with_heuristics() {
+        let items = items(&[8, 7, 6, 5, 4]);
+
+        let lpt = Plan::new(&items, 2, Strategy::Lpt);
+        assert_eq!(17, lpt.max_load());
+        let kk = Plan::new(&items, 2, Strategy::KarmarkarKarp);
+        assert_eq!(16, kk.max_load());
+        let exact = Plan::new(&items, 2, Strategy::Auto);
+        assert_eq!((Strategy::Exact, 15), (exact.strategy, exact.max_load()));
+    }
+
+    #[test]
+    fn can_plan_the_items_of_an_inventory() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        let inventory = Inventory::<u64>::from_reader(input.as_bytes()).unwrap();
+        let items = Item::all(&inventory);
+        assert_eq!(
+            Item {
+                elf: 2,
+                index: 1,
+                calories: 6000
+            },
+            items[5]
+        );
+
+        let plan = Plan::new(&items, 5, Strategy::Auto);
+        assert_eq!((11000, 11000), (plan.max_load(), plan.lower_bound));
+        assert_eq!(
+            vec![] as Vec<Item>,
+            Plan::new(&[], 3, Strategy::Auto).elves[0]
+        );
+
+        // the first elf carries more than a u32 can hold.
+        let inventory = Inventory::from_reader("4294967295\n1\n\n5".as_bytes()).unwrap();
+        let plan = Plan::new(&Item::all(&inventory), 2, Strategy::Auto);
+        assert_eq!(4294967295, plan.max_load());
+    }
+}
@@ -0,0 +1,201 @@
+//! Statistics over the calories carried by the elves.
+
+use std::fmt;
+
+use crate::day1::{Calories, Inventory};
+
+/// Percentiles included in the report.
+pub const PERCENTILES: [u8; 6] = [10, 25, 50, 75, 90, 99];
+
+/// Width of the longest bar of the histogram, in characters.
+const BAR_WIDTH: usize = 40;
+
+/// A range of totals and the number of elves carrying them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bin {
+    pub low: u128,
+    pub high: u128,
+    pub count: usize,
+}
+
+/// Summary of the totals of an inventory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation.
+    pub stddev: f64,
+    pub min: u128,
+    pub max: u128,
+    /// Nearest-rank percentiles, for each of [`PERCENTILES`].
+    pub percentiles: Vec<(u8, u128)>,
+    pub min_items: usize,
+    pub mean_items: f64,
+    pub max_items: usize,
+    pub histogram: Vec<Bin>,
+}
+
+impl Statistics {
+    /// Summarizes an inventory, splitting the histogram in at most `bins` ranges of the
+    /// same width. Returns `None` when there are no elves.
+    pub fn new<T: Calories>(inventory: &Inventory<T>, bins: usize) -> Option<Statistics> {
+        let mut totals: Vec<u128> = inventory
+            .totals()
+            .iter()
+            .map(|&total| total.into())
+            .collect();
+        totals.sort_unstable();
+        let (&min, &max) = (totals.first()?, totals.last()?);
+
+        let count = totals.len();
+        let mean = totals.iter().map(|&total| total as f64).sum::<f64>() / count as f64;
+        let median = match count % 2 {
+            0 => (totals[count / 2 - 1] as f64 + totals[count / 2] as f64) / 2.0,
+            _ => totals[count / 2] as f64,
+        };
+        let variance = totals
+            .iter()
+            .map(|&total| (total as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&percentile| {
+                let rank = (count * percentile as usize).div_ceil(100).max(1);
+                (percentile, totals[rank - 1])
+            })
+            .collect();
+
+        let items: Vec<usize> = inventory.items().iter().map(Vec::len).collect();
+        Some(Statistics {
+            count,
+            mean,
+            median,
+            stddev: variance.sqrt(),
+            min,
+            max,
+            percentiles,
+            min_items: items.iter().copied().min().unwrap_or(0),
+            mean_items: items.iter().sum::<usize>() as f64 / count as f64,
+            max_items: items.iter().copied().max().unwrap_or(0),
+            histogram: histogram(&totals, bins.max(1)),
+        })
+    }
+}
+
+/// Counts the sorted `totals` in at most `bins` ranges of the same width.
+fn histogram(totals: &[u128], bins: usize) -> Vec<Bin> {
+    let (min, max) = (totals[0], totals[totals.len() - 1]);
+    // the ceiling of (max - min + 1) / bins, which cannot overflow.
+    let width = (max - min) / bins as u128 + 1;
+
+    let mut histogram: Vec<Bin> = (0..=(max - min) / width)
+        .map(|bin| {
+            let low = min + bin * width;
+            Bin {
+                low,
+                high: low + (width - 1).min(max - low),
+                count: 0,
+            }
+        })
+        .collect();
+    for &total in totals {
+        histogram[((total - min) / width) as usize].count += 1;
+    }
+
+    histogram
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elves:          {}", self.count)?;
+        writeln!(f, "Mean:           {:.1}", self.mean)?;
+        writeln!(f, "Median:         {:.1}", self.median)?;
+        writeln!(f, "Std deviation:  {:.1}", self.stddev)?;
+        writeln!(f, "Min / max:      {} / {}", self.min, self.max)?;
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(percentile, total)| format!("p{percentile} {total}"))
+            .collect::<Vec<_>>();
+        writeln!(f, "Percentiles:    {}", percentiles.join(", "))?;
+        writeln!(
+            f,
+            "Items per elf:  min {}, mean {:.1}, max {}",
+            self.min_items, self.mean_items, self.max_items
+        )?;
+
+        writeln!(f)?;
+        let most = self
+            .histogram
+            .iter()
+            .map(|bin| bin.count)
+            .max()
+            .unwrap_or(0);
+        let digits = self.max.to_string().len();
+        for bin in &self.histogram {
+            let bar = match bin.count {
+                0 => 0,
+                count => (count * BAR_WIDTH / most).max(1),
+            };
+            writeln!(
+                f,
+                "{:>digits$} - {:>digits$} | {:<BAR_WIDTH$} {}",
+                bin.low,
+                bin.high,
+                "#".repeat(bar),
+                bin.count
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day1::stats::{Bin, Statistics};
+    use crate::day1::Inventory;
+
+    #[test]
+    fn can_summarize_inventory() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        let inventory = Inventory::<u32>::from_reader(input.as_bytes()).unwrap();
+        let stats = Statistics::new(&inventory, 2).unwrap();
+        let wide = Inventory::<u128>::from_reader(input.as_bytes()).unwrap();
+        assert_eq!(Some(&stats), Statistics::new(&wide, 2).as_ref());
+
+        assert_eq!(5, stats.count);
+        assert_eq!(11000.0, stats.mean);
+        assert_eq!(10000.0, stats.median);
+        assert!((stats.stddev - 6985.7).abs() < 0.1);
+        assert_eq!((4000, 24000), (stats.min, stats.max));
+        assert_eq!(
+            vec![(10, 4000), (25, 6000), (50, 10000)],
+            stats.percentiles[..3]
+        );
+        assert_eq!(
+            (1, 2.0, 3),
+            (stats.min_items, stats.mean_items, stats.max_items)
+        );
+        assert_eq!(
+            vec![
+                Bin {
+                    low: 4000,
+                    high: 14000,
+                    count: 4
+                },
+                Bin {
+                    low: 14001,
+                    high: 24000,
+                    count: 1
+                },
+            ],
+            stats.histogram
+        );
+
+        assert!(Statistics::new(&Inventory::<u32>::default(), 10).is_none());
+    }
+}
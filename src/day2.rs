@@ -0,0 +1,258 @@
+use std::io::BufRead;
+
+use itertools::Itertools;
+
+pub mod analysis;
+pub mod breakdown;
+pub mod cipher;
+pub mod decoder;
+pub mod league;
+pub mod rules;
+pub mod scoring;
+pub mod simulation;
+
+use crate::day2::decoder::{GuideDecoder, OutcomeDecoder, ShapeDecoder};
+use crate::day2::rules::{Rules, Shape};
+use crate::day2::scoring::Scoring;
+use crate::error::column_of;
+use crate::{debug, Error, Result, Solution};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GameResult {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl GameResult {
+    /// The points a player gets for the result of a game.
+    pub fn points(self) -> u32 {
+        Rules::classic().outcome_points(self)
+    }
+}
+
+/// A shape of the classic game, whose rules are [`Rules::classic`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GameElement {
+    Rock,
+    Scissors,
+    Paper,
+}
+
+impl GameElement {
+    /// Every shape, in the order of their points.
+    pub const ALL: [GameElement; 3] =
+        [GameElement::Rock, GameElement::Paper, GameElement::Scissors];
+
+    /// The shape of the classic rules.
+    pub fn shape(self) -> Shape {
+        match self {
+            GameElement::Rock => Shape(0),
+            GameElement::Paper => Shape(1),
+            GameElement::Scissors => Shape(2),
+        }
+    }
+
+    pub fn from_shape(shape: Shape) -> GameElement {
+        match shape.0 {
+            0 => GameElement::Rock,
+            1 => GameElement::Paper,
+            _ => GameElement::Scissors,
+        }
+    }
+
+    /// The result of playing self against other.
+    pub fn play(self, other: GameElement) -> GameResult {
+        Rules::classic().play(self.shape(), other.shape())
+    }
+
+    pub fn score(&self) -> u32 {
+        Rules::classic().shape_points(self.shape())
+    }
+
+    /// The shape a letter of either column of the guide stands for.
+    pub fn parse(letter: &str) -> Option<GameElement> {
+        let rules = Rules::classic();
+        rules
+            .oponent_shape(letter)
+            .or_else(|| rules.player_shape(letter))
+            .map(GameElement::from_shape)
+    }
+
+    fn element_for_result(&self, expected_outcome: GameResult) -> GameElement {
+        let shape = Rules::classic().shape_for_result(self.shape(), expected_outcome);
+        GameElement::from_shape(shape)
+    }
+}
+
+#[derive(Debug)]
+pub struct Tournament(pub Vec<(GameElement, GameElement)>);
+
+/// Splits a line of the strategy guide like "A Y" into its two columns.
+fn split_columns(line: &str) -> Result<(&str, &str)> {
+    match line.split(' ').collect_vec()[..] {
+        [oponent, player] => Ok((oponent, player)),
+        _ => Err(Error::parse(2, 1, line, "expected two columns")),
+    }
+}
+
+fn parse_element(line: &str, letter: &str, scoring: &Scoring) -> Result<GameElement> {
+    scoring
+        .shape(letter)
+        .ok_or_else(|| Error::parse(2, column_of(line, letter), letter, "unknown shape"))
+}
+
+/// Reads a line of the guide as a `(player, oponent)` game, with `decoder` reading its
+/// second column.
+fn decode_game(
+    line: &str,
+    decoder: &dyn GuideDecoder,
+    scoring: &Scoring,
+) -> Result<(GameElement, GameElement)> {
+    let (oponent, letter) = split_columns(line)?;
+    let oponent = parse_element(line, oponent, scoring)?;
+    let player = decoder.decode(oponent, letter, scoring).ok_or_else(|| {
+        let reason = format!("unknown {}", decoder.column());
+        Error::parse(2, column_of(line, letter), letter, reason)
+    })?;
+    Ok((player, oponent))
+}
+
+impl Tournament {
+    pub fn from_reader(reader: impl BufRead) -> Result<Tournament> {
+        Tournament::from_reader_with(reader, Scoring::classic())
+    }
+
+    pub fn from_reader_part2(reader: impl BufRead) -> Result<Tournament> {
+        Tournament::from_reader_part2_with(reader, Scoring::classic())
+    }
+
+    /// Reads a guide where the second column is the shape to play, with the letters of
+    /// `scoring`.
+    pub fn from_reader_with(reader: impl BufRead, scoring: &Scoring) -> Result<Tournament> {
+        Tournament::decode(reader, &ShapeDecoder, scoring)
+    }
+
+    /// Reads a guide where the second column is the result the game should have, with
+    /// the letters of `scoring`.
+    pub fn from_reader_part2_with(reader: impl BufRead, scoring: &Scoring) -> Result<Tournament> {
+        Tournament::decode(reader, &OutcomeDecoder, scoring)
+    }
+
+    /// Reads a guide with `decoder` reading its second column, and the letters of
+    /// `scoring`.
+    pub fn decode(
+        reader: impl BufRead,
+        decoder: &dyn GuideDecoder,
+        scoring: &Scoring,
+    ) -> Result<Tournament> {
+        let mut games: Vec<(GameElement, GameElement)> = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let game =
+                decode_game(&line?, decoder, scoring).map_err(|err| err.on_line(index + 1))?;
+            games.push(game);
+        }
+        debug!(
+            "read {} games with the {} decoder",
+            games.len(),
+            decoder.name()
+        );
+        Ok(Tournament(games))
+    }
+
+    pub fn calculate_score(&self) -> u64 {
+        self.score_with(Scoring::classic())
+    }
+
+    /// The score of the tournament with the points of `scoring`.
+    pub fn score_with(&self, scoring: &Scoring) -> u64 {
+        self.0
+            .iter()
+            .map(|&(player, oponent)| u64::from(scoring.score(player, oponent)))
+            .sum()
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    /// The strategy guide read with both meanings of its second column: the
+    /// shape to play, and the result the game should have.
+    type Input = (Tournament, Tournament);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(mut reader: impl BufRead) -> Result<Self::Input> {
+        let mut guide = String::new();
+        reader.read_to_string(&mut guide)?;
+
+        Ok((
+            Tournament::from_reader(guide.as_bytes())?,
+            Tournament::from_reader_part2(guide.as_bytes())?,
+        ))
+    }
+
+    fn part1(input: &Self::Input) -> u64 {
+        input.0.calculate_score()
+    }
+
+    fn part2(input: &Self::Input) -> u64 {
+        input.1.calculate_score()
+    }
+
+    fn stream(reader: impl BufRead) -> Result<(u64, u64)> {
+        let scoring = Scoring::classic();
+        let (mut part1, mut part2) = (0, 0);
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let (player, oponent) =
+                decode_game(&line, &ShapeDecoder, scoring).map_err(|err| err.on_line(index + 1))?;
+            part1 += u64::from(scoring.score(player, oponent));
+            let (player, oponent) = decode_game(&line, &OutcomeDecoder, scoring)
+                .map_err(|err| err.on_line(index + 1))?;
+            part2 += u64::from(scoring.score(player, oponent));
+        }
+
+        Ok((part1, part2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::day2::Tournament;
+
+    #[test]
+    fn part2() {
+        let input = "A Y\nB X\nC Z";
+        let reader = BufReader::new(input.as_bytes());
+        let tournament = Tournament::from_reader_part2(reader).unwrap();
+
+        assert_eq!(12, tournament.calculate_score());
+    }
+
+    #[test]
+    fn reports_unknown_letters() {
+        let input = "A Y\nB W\nC Z";
+        let err = Tournament::from_reader(BufReader::new(input.as_bytes())).unwrap_err();
+        assert_eq!(
+            "day 2, line 2, column 3: unknown shape: 'W'",
+            err.to_string()
+        );
+
+        let err = Tournament::from_reader_part2(BufReader::new(input.as_bytes())).unwrap_err();
+        assert_eq!(
+            "day 2, line 2, column 3: unknown outcome: 'W'",
+            err.to_string()
+        );
+
+        let err = Tournament::from_reader("A Y Z".as_bytes()).unwrap_err();
+        assert_eq!(
+            "day 2, line 1, column 1: expected two columns: 'A Y Z'",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! How well a strategy guide plays against the shapes the oponent is going to play: the
+//! best and worst scores it could have, and what the equilibrium of the game would give.
+
+use std::fmt;
+
+use crate::day2::scoring::Scoring;
+use crate::day2::{GameElement, Tournament};
+
+/// Tolerance of the checks of the equilibrium, which is computed with floats.
+const EPSILON: f64 = 1e-9;
+
+/// A strategy choosing each shape with a probability, indexed like [`GameElement::ALL`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MixedStrategy {
+    pub probabilities: [f64; 3],
+    /// The expected score of a round, when both players play the strategy.
+    pub value: f64,
+}
+
+impl MixedStrategy {
+    /// The symmetric Nash equilibrium of a game where each player scores with `scoring`:
+    /// a strategy that no shape scores better against. It is found by trying every
+    /// support, from all three shapes down to a single one.
+    pub fn nash(scoring: &Scoring) -> MixedStrategy {
+        let payoff = GameElement::ALL
+            .map(|player| GameElement::ALL.map(|oponent| scoring.score(player, oponent) as f64));
+
+        let mut supports: Vec<Vec<usize>> = (1u8..8)
+            .map(|mask| (0..3).filter(|shape| mask & (1 << shape) != 0).collect())
+            .collect();
+        supports.sort_by_key(|support| std::cmp::Reverse(support.len()));
+
+        supports
+            .iter()
+            .find_map(|support| equilibrium(&payoff, support))
+            .expect("every finite game has an equilibrium")
+    }
+}
+
+/// The equilibrium playing exactly the shapes of `support`, if there is one: every shape
+/// of the support scores `value` against it, and no other shape scores more.
+fn equilibrium(payoff: &[[f64; 3]; 3], support: &[usize]) -> Option<MixedStrategy> {
+    // unknowns: the probability of each shape of the support, then the value.
+    let k = support.len();
+    let mut system = vec![vec![0.0; k + 2]; k + 1];
+    for (row, &player) in support.iter().enumerate() {
+        for (column, &oponent) in support.iter().enumerate() {
+            system[row][column] = payoff[player][oponent];
+        }
+        system[row][k] = -1.0;
+    }
+    for coefficient in &mut system[k][..k] {
+        *coefficient = 1.0;
+    }
+    system[k][k + 1] = 1.0;
+
+    let solution = solve(system)?;
+    let mut probabilities = [0.0; 3];
+    for (&shape, &probability) in support.iter().zip(&solution) {
+        if probability < -EPSILON {
+            return None;
+        }
+        probabilities[shape] = probability.max(0.0);
+    }
+    let value = solution[k];
+
+    let best_response = (0..3).all(|player| {
+        let score: f64 = (0..3)
+            .map(|oponent| payoff[player][oponent] * probabilities[oponent])
+            .sum();
+        score <= value + EPSILON
+    });
+    best_response.then_some(MixedStrategy {
+        probabilities,
+        value,
+    })
+}
+
+/// Solves a square linear system, given as rows of coefficients followed by the constant,
+/// with Gaussian elimination. Returns `None` if the system is singular.
+fn solve(mut system: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let n = system.len();
+    for column in 0..n {
+        let pivot = (column..n)
+            .max_by(|&a, &b| system[a][column].abs().total_cmp(&system[b][column].abs()))?;
+        if system[pivot][column].abs() < EPSILON {
+            return None;
+        }
+        system.swap(column, pivot);
+
+        let pivot = system[column].clone();
+        for (row, coefficients) in system.iter_mut().enumerate() {
+            if row != column {
+                let factor = coefficients[column] / pivot[column];
+                for (coefficient, pivot) in coefficients[column..].iter_mut().zip(&pivot[column..])
+                {
+                    *coefficient -= factor * pivot;
+                }
+            }
+        }
+    }
+
+    Some(
+        (0..n)
+            .map(|row| system[row][n] / system[row][row])
+            .collect(),
+    )
+}
+
+/// The scores a tournament could have, given the shapes played by the oponent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub rounds: usize,
+    /// The score of the guide as written.
+    pub written: u64,
+    /// The score of playing the best shape every round.
+    pub best: u64,
+    /// The score of playing the worst shape every round.
+    pub worst: u64,
+    pub nash: MixedStrategy,
+    /// The expected score of playing the equilibrium every round, against the shapes of
+    /// the oponent.
+    pub nash_score: f64,
+}
+
+impl Analysis {
+    pub fn new(tournament: &Tournament, scoring: &Scoring) -> Analysis {
+        let nash = MixedStrategy::nash(scoring);
+        let scores =
+            |oponent| GameElement::ALL.map(|player| u64::from(scoring.score(player, oponent)));
+        let oponents = tournament.0.iter().map(|&(_, oponent)| oponent);
+
+        Analysis {
+            rounds: tournament.0.len(),
+            written: tournament.score_with(scoring),
+            best: oponents
+                .clone()
+                .map(|oponent| scores(oponent).into_iter().max().unwrap())
+                .sum(),
+            worst: oponents
+                .clone()
+                .map(|oponent| scores(oponent).into_iter().min().unwrap())
+                .sum(),
+            nash_score: oponents
+                .map(|oponent| {
+                    scores(oponent)
+                        .into_iter()
+                        .zip(nash.probabilities)
+                        .map(|(score, probability)| score as f64 * probability)
+                        .sum::<f64>()
+                })
+                .sum(),
+            nash,
+        }
+    }
+
+    /// Where the guide stands between the worst and the best scores, from 0 to 1.
+    pub fn efficiency(&self) -> f64 {
+        if self.best == self.worst {
+            return 1.0;
+        }
+        (self.written - self.worst) as f64 / (self.best - self.worst) as f64
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rounds = self.rounds.max(1) as f64;
+        let row = |f: &mut fmt::Formatter<'_>, name: &str, score: f64| {
+            writeln!(f, "{name:<12} {score:>10.0} {:>10.2}", score / rounds)
+        };
+
+        writeln!(f, "Rounds: {}", self.rounds)?;
+        writeln!(f)?;
+        writeln!(f, "{:<12} {:>10} {:>10}", "Strategy", "Score", "Per round")?;
+        row(f, "Best", self.best as f64)?;
+        row(f, "As written", self.written as f64)?;
+        row(f, "Nash", self.nash_score)?;
+        row(f, "Worst", self.worst as f64)?;
+        writeln!(f)?;
+        writeln!(f, "Efficiency: {:.1}%", self.efficiency() * 100.0)?;
+
+        let mix = GameElement::ALL
+            .iter()
+            .zip(self.nash.probabilities)
+            .map(|(element, probability)| format!("{element:?} {:.1}%", probability * 100.0))
+            .collect::<Vec<_>>();
+        writeln!(f, "Nash mix:   {}", mix.join(", "))?;
+        writeln!(f, "Game value: {:.2} per round", self.nash.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day2::analysis::{Analysis, MixedStrategy};
+    use crate::day2::scoring::Scoring;
+    use crate::day2::Tournament;
+
+    #[test]
+    fn can_find_the_equilibrium() {
+        let nash = MixedStrategy::nash(Scoring::classic());
+        let expected = [4.0 / 9.0, 1.0 / 9.0, 4.0 / 9.0];
+        for (probability, expected) in nash.probabilities.iter().zip(expected) {
+            assert!((probability - expected).abs() < 1e-9);
+        }
+        assert!((nash.value - 5.0).abs() < 1e-9);
+
+        // paper is worth so much that it is always the best shape to play.
+        let scoring = Scoring::from_key_values("shapes.paper = 10").unwrap();
+        let nash = MixedStrategy::nash(&scoring);
+        assert_eq!([0.0, 1.0, 0.0], nash.probabilities);
+        assert_eq!(13.0, nash.value);
+    }
+
+    #[test]
+    fn can_analyze_tournaments() {
+        let tournament = Tournament::from_reader("A Y\nB X\nC Z".as_bytes()).unwrap();
+        let analysis = Analysis::new(&tournament, Scoring::classic());
+
+        assert_eq!(3, analysis.rounds);
+        assert_eq!(15, analysis.written);
+        assert_eq!(8 + 9 + 7, analysis.best);
+        assert_eq!(3 + 1 + 2, analysis.worst);
+        assert_eq!(0.5, analysis.efficiency());
+    }
+
+    #[test]
+    fn can_score_the_equilibrium_against_the_oponent() {
+        // the oponent always plays rock, which the equilibrium does not expect.
+        let tournament = Tournament::from_reader("A X\nA Y\nA Z".as_bytes()).unwrap();
+        let analysis = Analysis::new(&tournament, Scoring::classic());
+
+        let per_round = 4.0 / 9.0 * 4.0 + 1.0 / 9.0 * 8.0 + 4.0 / 9.0 * 3.0;
+        assert!((analysis.nash_score - 3.0 * per_round).abs() < 1e-9);
+        assert!((analysis.nash_score - 3.0 * analysis.nash.value).abs() > 1.0);
+    }
+}
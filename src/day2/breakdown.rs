@@ -0,0 +1,183 @@
+//! The score of a tournament round by round, to audit disputed guides.
+
+use std::fmt;
+
+use crate::day2::scoring::Scoring;
+use crate::day2::{GameElement, GameResult, Tournament};
+
+/// A round of a tournament and the points it gives the player.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Round {
+    /// The 1-based number of the round, which is also its line in the guide.
+    pub number: usize,
+    pub player: GameElement,
+    pub oponent: GameElement,
+    pub result: GameResult,
+    pub shape_points: u32,
+    pub outcome_points: u32,
+    /// The score of the tournament up to this round, included.
+    pub running_score: u64,
+}
+
+/// The results of the rounds where the player played a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeSummary {
+    pub shape: GameElement,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakdown {
+    pub rounds: Vec<Round>,
+    /// A summary for each shape, indexed like [`GameElement::ALL`].
+    pub shapes: [ShapeSummary; 3],
+}
+
+impl Breakdown {
+    pub fn new(tournament: &Tournament, scoring: &Scoring) -> Breakdown {
+        let mut shapes = GameElement::ALL.map(|shape| ShapeSummary {
+            shape,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        });
+
+        let mut running_score = 0;
+        let mut rounds = vec![];
+        for (index, &(player, oponent)) in tournament.0.iter().enumerate() {
+            let result = player.play(oponent);
+            let shape_points = scoring.shape_points(player);
+            let outcome_points = scoring.outcome_points(result);
+            running_score += u64::from(shape_points) + u64::from(outcome_points);
+
+            let summary = &mut shapes[player.shape().0];
+            match result {
+                GameResult::Win => summary.wins += 1,
+                GameResult::Draw => summary.draws += 1,
+                GameResult::Lose => summary.losses += 1,
+            }
+
+            rounds.push(Round {
+                number: index + 1,
+                player,
+                oponent,
+                result,
+                shape_points,
+                outcome_points,
+                running_score,
+            });
+        }
+
+        Breakdown { rounds, shapes }
+    }
+
+    /// The score of the whole tournament.
+    pub fn total(&self) -> u64 {
+        self.rounds.last().map_or(0, |round| round.running_score)
+    }
+
+    /// The rounds as CSV, with a header line.
+    pub fn rounds_csv(&self) -> String {
+        let mut csv =
+            "round,player,oponent,outcome,shape_points,outcome_points,running_score\n".to_owned();
+        for round in &self.rounds {
+            csv += &format!(
+                "{},{:?},{:?},{:?},{},{},{}\n",
+                round.number,
+                round.player,
+                round.oponent,
+                round.result,
+                round.shape_points,
+                round.outcome_points,
+                round.running_score
+            );
+        }
+        csv
+    }
+
+    /// The summary of each shape as a table.
+    pub fn shapes_table(&self) -> String {
+        let mut table =
+            "Shape    | Wins | Draws | Losses\n---------+------+-------+-------\n".to_owned();
+        for summary in &self.shapes {
+            table += &format!(
+                "{:<8} | {:>4} | {:>5} | {:>6}\n",
+                format!("{:?}", summary.shape),
+                summary.wins,
+                summary.draws,
+                summary.losses
+            );
+        }
+        table
+    }
+
+    /// The summary of each shape as CSV, with a header line.
+    pub fn shapes_csv(&self) -> String {
+        let mut csv = "shape,wins,draws,losses\n".to_owned();
+        for summary in &self.shapes {
+            csv += &format!(
+                "{:?},{},{},{}\n",
+                summary.shape, summary.wins, summary.draws, summary.losses
+            );
+        }
+        csv
+    }
+}
+
+impl fmt::Display for Breakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Round | Player   | Oponent  | Outcome | Shape | Outcome pts | Running"
+        )?;
+        writeln!(
+            f,
+            "------+----------+----------+---------+-------+-------------+--------"
+        )?;
+        for round in &self.rounds {
+            writeln!(
+                f,
+                "{:>5} | {:<8} | {:<8} | {:<7} | {:>5} | {:>11} | {:>7}",
+                round.number,
+                format!("{:?}", round.player),
+                format!("{:?}", round.oponent),
+                format!("{:?}", round.result),
+                round.shape_points,
+                round.outcome_points,
+                round.running_score
+            )?;
+        }
+        writeln!(f, "Total: {}", self.total())?;
+        writeln!(f)?;
+        write!(f, "{}", self.shapes_table())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day2::breakdown::Breakdown;
+    use crate::day2::scoring::Scoring;
+    use crate::day2::Tournament;
+
+    #[test]
+    fn can_break_tournaments_down() {
+        let tournament = Tournament::from_reader("A Y\nB X\nC Z\nA Y".as_bytes()).unwrap();
+        let breakdown = Breakdown::new(&tournament, Scoring::classic());
+
+        assert_eq!(tournament.calculate_score(), breakdown.total());
+        assert_eq!(
+            "round,player,oponent,outcome,shape_points,outcome_points,running_score\n\
+             1,Paper,Rock,Win,2,6,8\n\
+             2,Rock,Paper,Lose,1,0,9\n\
+             3,Scissors,Scissors,Draw,3,3,15\n\
+             4,Paper,Rock,Win,2,6,23\n",
+            breakdown.rounds_csv()
+        );
+        assert_eq!(
+            "shape,wins,draws,losses\nRock,0,0,1\nPaper,2,0,0\nScissors,0,1,0\n",
+            breakdown.shapes_csv()
+        );
+    }
+}
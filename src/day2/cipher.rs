@@ -0,0 +1,165 @@
+//! Deduces what the second column of a strategy guide stands for from the score it is
+//! claimed to give, for guides whose encoding is unknown.
+//!
+//! The letters X, Y and Z are assumed to stand for three different shapes, or for three
+//! different outcomes, which makes twelve ciphers to try.
+
+use std::fmt;
+use std::io::BufRead;
+
+use itertools::Itertools;
+
+use crate::day2::rules::Rules;
+use crate::day2::{split_columns, GameElement, GameResult, Tournament};
+use crate::error::column_of;
+use crate::{debug, Error, Result};
+
+const LETTERS: [&str; 3] = ["X", "Y", "Z"];
+
+/// What X, Y and Z stand for, in this order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cipher {
+    Shapes([GameElement; 3]),
+    Outcomes([GameResult; 3]),
+}
+
+impl Cipher {
+    /// Every cipher where the three letters stand for different shapes or outcomes.
+    pub fn all() -> Vec<Cipher> {
+        let elements = ["A", "B", "C"].map(|letter| GameElement::parse(letter).unwrap());
+        let results = [GameResult::Lose, GameResult::Draw, GameResult::Win];
+
+        let shapes = elements
+            .into_iter()
+            .permutations(3)
+            .map(|shapes| Cipher::Shapes([shapes[0], shapes[1], shapes[2]]));
+        let outcomes = results
+            .into_iter()
+            .permutations(3)
+            .map(|outcomes| Cipher::Outcomes([outcomes[0], outcomes[1], outcomes[2]]));
+        shapes.chain(outcomes).collect()
+    }
+
+    /// The shape to play against `oponent` when the second column is `LETTERS[letter]`.
+    fn player(&self, oponent: GameElement, letter: usize) -> GameElement {
+        match self {
+            Cipher::Shapes(shapes) => shapes[letter],
+            Cipher::Outcomes(outcomes) => oponent.element_for_result(outcomes[letter]),
+        }
+    }
+}
+
+impl fmt::Display for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meanings = match self {
+            Cipher::Shapes(shapes) => shapes.map(|shape| format!("{shape:?}")),
+            Cipher::Outcomes(outcomes) => outcomes.map(|outcome| format!("{outcome:?}")),
+        };
+        let assignments = LETTERS
+            .iter()
+            .zip(meanings)
+            .map(|(letter, meaning)| format!("{letter}={meaning}"));
+        write!(f, "{}", assignments.format(" "))
+    }
+}
+
+/// A guide whose second column is not decoded yet.
+#[derive(Debug)]
+pub struct EncodedGuide {
+    /// The shape of the oponent, and the index of the letter of the second column.
+    games: Vec<(GameElement, usize)>,
+}
+
+impl EncodedGuide {
+    pub fn from_reader(reader: impl BufRead) -> Result<EncodedGuide> {
+        let mut games = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let game = parse_encoded(&line?).map_err(|err| err.on_line(index + 1))?;
+            games.push(game);
+        }
+        Ok(EncodedGuide { games })
+    }
+
+    /// The tournament the guide describes when read with `cipher`.
+    pub fn decode(&self, cipher: &Cipher) -> Tournament {
+        let games = self
+            .games
+            .iter()
+            .map(|&(oponent, letter)| (cipher.player(oponent, letter), oponent))
+            .collect();
+        Tournament(games)
+    }
+
+    /// The ciphers whose tournament scores `claimed` points.
+    pub fn deduce(&self, claimed: u64) -> Vec<Cipher> {
+        Cipher::all()
+            .into_iter()
+            .filter(|cipher| {
+                let score = self.decode(cipher).calculate_score();
+                debug!("{cipher} scores {score}");
+                score == claimed
+            })
+            .collect()
+    }
+}
+
+fn parse_encoded(line: &str) -> Result<(GameElement, usize)> {
+    let (oponent, letter) = split_columns(line)?;
+    // only the second column is encoded: the oponent plays A, B or C.
+    let oponent = Rules::classic()
+        .oponent_shape(oponent)
+        .map(GameElement::from_shape)
+        .ok_or_else(|| Error::parse(2, column_of(line, oponent), oponent, "expected A, B or C"))?;
+    let letter = LETTERS
+        .iter()
+        .position(|&known| known == letter)
+        .ok_or_else(|| Error::parse(2, column_of(line, letter), letter, "expected X, Y or Z"))?;
+    Ok((oponent, letter))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day2::cipher::{Cipher, EncodedGuide};
+    use crate::day2::{GameElement, GameResult};
+
+    #[test]
+    fn can_deduce_ciphers() {
+        let guide = EncodedGuide::from_reader("A Y\nB X\nC Z".as_bytes()).unwrap();
+        assert_eq!(12, Cipher::all().len());
+
+        let ciphers = guide.deduce(15);
+        assert!(ciphers.contains(&Cipher::Shapes([
+            GameElement::Rock,
+            GameElement::Paper,
+            GameElement::Scissors
+        ])));
+        let ciphers = guide.deduce(12);
+        assert!(ciphers.contains(&Cipher::Outcomes([
+            GameResult::Lose,
+            GameResult::Draw,
+            GameResult::Win
+        ])));
+        for cipher in guide.deduce(12) {
+            assert_eq!(12, guide.decode(&cipher).calculate_score());
+        }
+        assert!(guide.deduce(1000).is_empty());
+
+        let cipher = Cipher::Outcomes([GameResult::Lose, GameResult::Draw, GameResult::Win]);
+        assert_eq!("X=Lose Y=Draw Z=Win", cipher.to_string());
+    }
+
+    #[test]
+    fn reports_unknown_letters() {
+        let err = EncodedGuide::from_reader("A Y\nB W".as_bytes()).unwrap_err();
+        assert_eq!(
+            "day 2, line 2, column 3: expected X, Y or Z: 'W'",
+            err.to_string()
+        );
+
+        let err = EncodedGuide::from_reader("A Y\nX Y".as_bytes()).unwrap_err();
+        assert_eq!(
+            "day 2, line 2, column 1: expected A, B or C: 'X'",
+            err.to_string()
+        );
+    }
+}
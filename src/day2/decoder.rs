@@ -0,0 +1,149 @@
+//! The readings of the second column of the strategy guide. The puzzle has two of them,
+//! and more can be registered in [`Decoders`] and chosen by name.
+
+use crate::day2::scoring::Scoring;
+use crate::day2::GameElement;
+use crate::trace;
+
+/// A reading of the second column of the strategy guide.
+pub trait GuideDecoder: Send + Sync {
+    /// The name the decoder is chosen by.
+    fn name(&self) -> &str;
+
+    /// What the second column holds, like "shape", to report the letters that cannot
+    /// be decoded.
+    fn column(&self) -> &str;
+
+    /// The shape to play against `oponent`, given the letter of the second column, or
+    /// `None` if the letter means nothing to the decoder.
+    fn decode(&self, oponent: GameElement, letter: &str, scoring: &Scoring) -> Option<GameElement>;
+}
+
+/// The second column is the shape to play, as in the first part.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShapeDecoder;
+
+impl GuideDecoder for ShapeDecoder {
+    fn name(&self) -> &str {
+        "shape"
+    }
+
+    fn column(&self) -> &str {
+        "shape"
+    }
+
+    fn decode(&self, _: GameElement, letter: &str, scoring: &Scoring) -> Option<GameElement> {
+        scoring.shape(letter)
+    }
+}
+
+/// The second column is the result the game should have, as in the second part.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutcomeDecoder;
+
+impl GuideDecoder for OutcomeDecoder {
+    fn name(&self) -> &str {
+        "outcome"
+    }
+
+    fn column(&self) -> &str {
+        "outcome"
+    }
+
+    fn decode(&self, oponent: GameElement, letter: &str, scoring: &Scoring) -> Option<GameElement> {
+        let result = scoring.result(letter)?;
+        let player = oponent.element_for_result(result);
+        trace!("{oponent:?} and {letter}: playing {player:?}");
+        Some(player)
+    }
+}
+
+/// The decoders that can be chosen by name: [`ShapeDecoder`] and [`OutcomeDecoder`],
+/// and those registered on top of them.
+pub struct Decoders(Vec<Box<dyn GuideDecoder>>);
+
+impl Default for Decoders {
+    fn default() -> Decoders {
+        Decoders(vec![Box::new(ShapeDecoder), Box::new(OutcomeDecoder)])
+    }
+}
+
+impl Decoders {
+    /// Adds a decoder, replacing the one with the same name if there is one.
+    pub fn register(&mut self, decoder: Box<dyn GuideDecoder>) {
+        match self
+            .0
+            .iter()
+            .position(|known| known.name() == decoder.name())
+        {
+            Some(index) => self.0[index] = decoder,
+            None => self.0.push(decoder),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn GuideDecoder> {
+        self.0
+            .iter()
+            .find(|decoder| decoder.name() == name)
+            .map(|decoder| decoder.as_ref())
+    }
+
+    /// The names of the decoders, in the order they were registered.
+    pub fn names(&self) -> Vec<&str> {
+        self.0.iter().map(|decoder| decoder.name()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day2::decoder::{Decoders, GuideDecoder};
+    use crate::day2::rules::Shape;
+    use crate::day2::scoring::Scoring;
+    use crate::day2::{GameElement, Tournament};
+
+    /// The second column says how far to move from the oponent's shape in the order
+    /// rock, paper, scissors: X stays, Y moves one shape and Z two.
+    struct RotationDecoder;
+
+    impl GuideDecoder for RotationDecoder {
+        fn name(&self) -> &str {
+            "rotation"
+        }
+
+        fn column(&self) -> &str {
+            "rotation"
+        }
+
+        fn decode(&self, oponent: GameElement, letter: &str, _: &Scoring) -> Option<GameElement> {
+            let steps = ["X", "Y", "Z"].iter().position(|&step| step == letter)?;
+            let shape = Shape((oponent.shape().0 + steps) % 3);
+            Some(GameElement::from_shape(shape))
+        }
+    }
+
+    #[test]
+    fn can_register_decoders() {
+        let mut decoders = Decoders::default();
+        decoders.register(Box::new(RotationDecoder));
+        assert_eq!(vec!["shape", "outcome", "rotation"], decoders.names());
+
+        let guide = "A Y\nB X\nC Z";
+        let scoring = Scoring::classic();
+        let score = |name| {
+            let decoder = decoders.get(name).unwrap();
+            let tournament = Tournament::decode(guide.as_bytes(), decoder, scoring).unwrap();
+            tournament.score_with(scoring)
+        };
+        assert_eq!(15, score("shape"));
+        assert_eq!(12, score("outcome"));
+        // paper against rock, paper against paper, paper against scissors.
+        assert_eq!((2 + 6) + (2 + 3) + 2, score("rotation"));
+        assert!(decoders.get("cipher").is_none());
+
+        let err = Tournament::decode("A W".as_bytes(), &RotationDecoder, scoring).unwrap_err();
+        assert_eq!(
+            "day 2, line 1, column 3: unknown rotation: 'W'",
+            err.to_string()
+        );
+    }
+}
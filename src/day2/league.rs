@@ -0,0 +1,329 @@
+//! A round-robin league between strategy guides: every guide plays every other one,
+//! round by round, and the guides are ranked on the matches they won.
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::path::Path;
+
+use crate::day2::scoring::Scoring;
+use crate::day2::{GameElement, GameResult, Tournament};
+use crate::input::InputSource;
+use crate::{debug, Result};
+
+/// League points of a match won, and of a match drawn.
+pub const WIN_POINTS: u32 = 3;
+pub const DRAW_POINTS: u32 = 1;
+
+/// A guide taking part in the league, and the shapes it plays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub shapes: Vec<GameElement>,
+}
+
+impl Entry {
+    /// Reads a guide whose second column is the shape to play, named after its file.
+    /// Errors tell which guide they come from.
+    pub fn load(path: &Path, scoring: &Scoring) -> Result<Entry> {
+        let reader = InputSource::File(path.to_owned()).open()?;
+        let tournament =
+            Tournament::from_reader_with(reader, scoring).map_err(|err| err.in_file(path))?;
+        let name = path.file_stem().unwrap_or(path.as_os_str());
+        Ok(Entry {
+            name: name.to_string_lossy().into_owned(),
+            shapes: tournament.0.iter().map(|&(player, _)| player).collect(),
+        })
+    }
+}
+
+/// A match between two guides, which lasts as many rounds as the shorter one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Match {
+    /// The indexes of both guides in the league.
+    pub guides: [usize; 2],
+    /// The rounds won by each guide.
+    pub wins: [usize; 2],
+    pub draws: usize,
+    /// The score of each guide over the match.
+    pub scores: [u64; 2],
+}
+
+impl Match {
+    fn play(guides: [usize; 2], entries: &[Entry], scoring: &Scoring) -> Match {
+        let [first, second] = guides.map(|guide| &entries[guide].shapes);
+        let mut played = Match {
+            guides,
+            wins: [0, 0],
+            draws: 0,
+            scores: [0, 0],
+        };
+        for (&first, &second) in first.iter().zip(second) {
+            match first.play(second) {
+                GameResult::Win => played.wins[0] += 1,
+                GameResult::Lose => played.wins[1] += 1,
+                GameResult::Draw => played.draws += 1,
+            }
+            played.scores[0] += u64::from(scoring.score(first, second));
+            played.scores[1] += u64::from(scoring.score(second, first));
+        }
+        played
+    }
+
+    /// The league points of `guide`, which must be one of the guides of the match.
+    pub fn points(&self, guide: usize) -> u32 {
+        let side = usize::from(self.guides[1] == guide);
+        match self.wins[side].cmp(&self.wins[1 - side]) {
+            std::cmp::Ordering::Greater => WIN_POINTS,
+            std::cmp::Ordering::Equal => DRAW_POINTS,
+            std::cmp::Ordering::Less => 0,
+        }
+    }
+}
+
+/// The line of a guide in the league table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Standing {
+    pub guide: usize,
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+    pub points: u32,
+    /// The points won against the guides with as many points, the first tie-breaker.
+    pub head_to_head: u32,
+    pub rounds_won: usize,
+    pub rounds_lost: usize,
+    /// The score of the guide over all its matches, under the scoring of the league.
+    pub score: u64,
+}
+
+impl Standing {
+    pub fn round_difference(&self) -> i64 {
+        self.rounds_won as i64 - self.rounds_lost as i64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct League {
+    pub entries: Vec<Entry>,
+    pub matches: Vec<Match>,
+    /// The standings, from the first guide to the last one.
+    pub table: Vec<Standing>,
+}
+
+impl League {
+    /// Plays every match of the league. Guides tied on points are ranked on the points
+    /// won against each other, then the difference of rounds won and lost, the rounds
+    /// won, the score, and finally their names.
+    pub fn new(entries: Vec<Entry>, scoring: &Scoring) -> League {
+        let n = entries.len();
+        let mut matches = vec![];
+        for first in 0..n {
+            for second in first + 1..n {
+                let played = Match::play([first, second], &entries, scoring);
+                debug!(
+                    "{} {} - {} {}",
+                    entries[first].name, played.wins[0], played.wins[1], entries[second].name
+                );
+                matches.push(played);
+            }
+        }
+
+        let mut table: Vec<Standing> = (0..n)
+            .map(|guide| Standing {
+                guide,
+                ..Standing::default()
+            })
+            .collect();
+        for played in &matches {
+            for (side, &guide) in played.guides.iter().enumerate() {
+                let standing = &mut table[guide];
+                let points = played.points(guide);
+                match points {
+                    WIN_POINTS => standing.won += 1,
+                    DRAW_POINTS => standing.drawn += 1,
+                    _ => standing.lost += 1,
+                }
+                standing.points += points;
+                standing.rounds_won += played.wins[side];
+                standing.rounds_lost += played.wins[1 - side];
+                standing.score += played.scores[side];
+            }
+        }
+
+        let points: Vec<u32> = table.iter().map(|standing| standing.points).collect();
+        for played in &matches {
+            let [first, second] = played.guides;
+            if points[first] == points[second] {
+                table[first].head_to_head += played.points(first);
+                table[second].head_to_head += played.points(second);
+            }
+        }
+
+        table.sort_by_key(|standing| {
+            (
+                Reverse(standing.points),
+                Reverse(standing.head_to_head),
+                Reverse(standing.round_difference()),
+                Reverse(standing.rounds_won),
+                Reverse(standing.score),
+                entries[standing.guide].name.clone(),
+            )
+        });
+
+        League {
+            entries,
+            matches,
+            table,
+        }
+    }
+
+    pub fn load(paths: &[impl AsRef<Path>], scoring: &Scoring) -> Result<League> {
+        let entries = paths
+            .iter()
+            .map(|path| Entry::load(path.as_ref(), scoring))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(League::new(entries, scoring))
+    }
+
+    fn name(&self, guide: usize) -> &str {
+        &self.entries[guide].name
+    }
+}
+
+impl fmt::Display for League {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+
+        writeln!(
+            f,
+            "Pos | {:<width$} | W | D | L | Pts | H2H | Rounds      | Diff  | Score",
+            "Guide"
+        )?;
+        writeln!(
+            f,
+            "----+-{}-+---+---+---+-----+-----+-------------+-------+------",
+            "-".repeat(width)
+        )?;
+        for (position, standing) in self.table.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>3} | {:<width$} | {} | {} | {} | {:>3} | {:>3} | {:>5}-{:<5} | {:>+5} | {}",
+                position + 1,
+                self.name(standing.guide),
+                standing.won,
+                standing.drawn,
+                standing.lost,
+                standing.points,
+                standing.head_to_head,
+                standing.rounds_won,
+                standing.rounds_lost,
+                standing.round_difference(),
+                standing.score
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Head to head (rounds won, draws):")?;
+        for played in &self.matches {
+            let [first, second] = played.guides;
+            writeln!(
+                f,
+                "{:>width$} {:>5} - {:<5} {:<width$} ({} draws)",
+                self.name(first),
+                played.wins[0],
+                played.wins[1],
+                self.name(second),
+                played.draws
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use crate::day2::league::{Entry, League};
+    use crate::day2::scoring::Scoring;
+    use crate::day2::GameElement::{self, Paper, Rock, Scissors};
+    use crate::Error;
+
+    fn entry(name: &str, shapes: &[GameElement]) -> Entry {
+        Entry {
+            name: name.to_owned(),
+            shapes: shapes.to_vec(),
+        }
+    }
+
+    fn ranking(league: &League) -> Vec<&str> {
+        league
+            .table
+            .iter()
+            .map(|standing| league.name(standing.guide))
+            .collect()
+    }
+
+    #[test]
+    fn can_rank_guides() {
+        let entries = vec![
+            entry("rocks", &[Rock; 3]),
+            entry("papers", &[Paper; 3]),
+            entry("scissors", &[Scissors; 3]),
+        ];
+        let league = League::new(entries, Scoring::classic());
+
+        // every guide wins a match, so the score decides.
+        assert_eq!(vec!["scissors", "papers", "rocks"], ranking(&league));
+        assert_eq!(3, league.matches.len());
+        let scissors = &league.table[0];
+        assert_eq!((1, 0, 1), (scissors.won, scissors.drawn, scissors.lost));
+        assert_eq!(3 * (3 + 6) + 3 * 3, scissors.score);
+    }
+
+    #[test]
+    fn can_break_ties_head_to_head() {
+        let entries = vec![
+            entry("a", &[Scissors, Rock, Scissors]),
+            entry("b", &[Paper; 3]),
+            entry("c", &[Paper, Paper, Rock]),
+            entry("d", &[Scissors; 3]),
+        ];
+        let league = League::new(entries, Scoring::classic());
+
+        // a and d both have 6 points, and d won more rounds, but a beat d.
+        let [a, d] = [&league.table[0], &league.table[1]];
+        assert_eq!(vec!["a", "d"], ranking(&league)[..2]);
+        assert_eq!((6, 6), (a.points, d.points));
+        assert_eq!((3, 0), (a.head_to_head, d.head_to_head));
+        assert!(a.round_difference() < d.round_difference());
+    }
+
+    #[test]
+    fn reports_the_invalid_guide() {
+        let good = std::env::temp_dir().join("aoc2022-day2-league-good.txt");
+        let bad = std::env::temp_dir().join("aoc2022-day2-league-bad.txt");
+        fs::write(&good, "A Y\nB X\n").unwrap();
+        fs::write(&bad, "A Y\nB Q\n").unwrap();
+
+        let err = League::load(&[&good, &bad], Scoring::classic()).unwrap_err();
+        assert_eq!(
+            format!(
+                "{}: day 2, line 2, column 3: unknown shape: 'Q'",
+                bad.display()
+            ),
+            err.to_string()
+        );
+
+        let missing = Path::new("missing-guide.txt");
+        let err = League::load(&[&good, missing], Scoring::classic()).unwrap_err();
+        assert!(matches!(err, Error::Input { path, .. } if path == missing));
+    }
+}
@@ -0,0 +1,365 @@
+//! Rule tables of cyclic games like rock paper scissors, where every shape beats as
+//! many shapes as it loses to.
+
+use std::io::BufRead;
+use std::sync::OnceLock;
+
+use crate::day2::{split_columns, GameResult};
+use crate::error::column_of;
+use crate::{Error, Result};
+
+/// A shape of a game, identified by its position in the [`Rules`] it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape(pub usize);
+
+/// What the second column of a strategy guide holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// The shape to play, as in the first part.
+    Shape,
+    /// The result the game should have, as in the second part.
+    Outcome,
+}
+
+/// The shapes of a game and which shape beats which.
+///
+/// Shapes are listed in the order of their points: the first one is worth 1 point, the
+/// second one 2 points, and so on. In a strategy guide, the oponent plays the letters
+/// from A, one for each shape in that order, and the player the letters up to Z: X, Y
+/// and Z in the classic game, and V to Z with five shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    names: Vec<String>,
+    /// `beats[a][b]` is true when `a` beats `b`.
+    beats: Vec<Vec<bool>>,
+}
+
+impl Rules {
+    /// Builds the rules of a game from the `(winner, loser)` pairs of its shapes. Every
+    /// pair of shapes must be decided, and every shape must beat half of the others.
+    pub fn new(names: Vec<String>, wins: &[(usize, usize)]) -> Result<Rules> {
+        let n = names.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(Error::config(format!(
+                "a balanced game needs an odd number of shapes, at least 3, not {n}"
+            )));
+        }
+
+        let mut beats = vec![vec![false; n]; n];
+        for &(winner, loser) in wins {
+            if winner >= n || loser >= n || winner == loser {
+                return Err(Error::config(format!(
+                    "invalid matchup {winner} beats {loser}"
+                )));
+            }
+            if beats[loser][winner] {
+                return Err(Error::config(format!(
+                    "{} and {} beat each other",
+                    names[winner], names[loser]
+                )));
+            }
+            beats[winner][loser] = true;
+        }
+
+        for (shape, row) in beats.iter().enumerate() {
+            let wins = row.iter().filter(|&&beats| beats).count();
+            if wins != n / 2 {
+                return Err(Error::config(format!(
+                    "{} beats {wins} shapes instead of {}",
+                    names[shape],
+                    n / 2
+                )));
+            }
+        }
+
+        Ok(Rules { names, beats })
+    }
+
+    /// A cyclic game with the shapes in the order of the cycle: every shape beats the
+    /// shapes that follow it at an odd distance, wrapping around.
+    pub fn cyclic(names: Vec<String>) -> Result<Rules> {
+        let n = names.len();
+        let wins: Vec<(usize, usize)> = (0..n)
+            .flat_map(|shape| (1..n).step_by(2).map(move |d| (shape, (shape + d) % n)))
+            .collect();
+        Rules::new(names, &wins)
+    }
+
+    /// Rock, paper and scissors.
+    pub fn classic() -> &'static Rules {
+        static CLASSIC: OnceLock<Rules> = OnceLock::new();
+        CLASSIC.get_or_init(|| {
+            let names = ["Rock", "Paper", "Scissors"].map(String::from).to_vec();
+            Rules::new(names, &[(0, 2), (2, 1), (1, 0)]).expect("the classic game is balanced")
+        })
+    }
+
+    /// Rock, paper, scissors, lizard and Spock.
+    pub fn rpsls() -> &'static Rules {
+        static RPSLS: OnceLock<Rules> = OnceLock::new();
+        RPSLS.get_or_init(|| {
+            let names = ["Rock", "Paper", "Scissors", "Lizard", "Spock"]
+                .map(String::from)
+                .to_vec();
+            let wins = [
+                (2, 1), // scissors cuts paper
+                (1, 0), // paper covers rock
+                (0, 3), // rock crushes lizard
+                (3, 4), // lizard poisons Spock
+                (4, 2), // Spock smashes scissors
+                (2, 3), // scissors decapitates lizard
+                (3, 1), // lizard eats paper
+                (1, 4), // paper disproves Spock
+                (4, 0), // Spock vaporizes rock
+                (0, 2), // rock crushes scissors
+            ];
+            Rules::new(names, &wins).expect("rock paper scissors lizard Spock is balanced")
+        })
+    }
+
+    /// The rules called `name`: `classic` or `rpsls`.
+    pub fn by_name(name: &str) -> Option<&'static Rules> {
+        match name {
+            "classic" => Some(Rules::classic()),
+            "rpsls" => Some(Rules::rpsls()),
+            _ => None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.len()).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.0]
+    }
+
+    /// Finds a shape by its name, ignoring case.
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.names
+            .iter()
+            .position(|shape| shape.eq_ignore_ascii_case(name))
+            .map(Shape)
+    }
+
+    /// The shape a letter of the oponent's column stands for.
+    pub fn oponent_shape(&self, letter: &str) -> Option<Shape> {
+        let index = letter_index(letter)?;
+        (index < self.len()).then_some(Shape(index))
+    }
+
+    /// The shape a letter of the player's column stands for, when it holds shapes.
+    pub fn player_shape(&self, letter: &str) -> Option<Shape> {
+        let first = 26usize.checked_sub(self.len())?;
+        let index = letter_index(letter)?.checked_sub(first)?;
+        (index < self.len()).then_some(Shape(index))
+    }
+
+    /// The result a letter of the player's column asks for, when it holds outcomes: X
+    /// to lose, Y to draw and Z to win.
+    pub fn result(letter: &str) -> Option<GameResult> {
+        match letter {
+            "X" => Some(GameResult::Lose),
+            "Y" => Some(GameResult::Draw),
+            "Z" => Some(GameResult::Win),
+            _ => None,
+        }
+    }
+
+    /// The result of `player` against `oponent`.
+    pub fn play(&self, player: Shape, oponent: Shape) -> GameResult {
+        if player == oponent {
+            GameResult::Draw
+        } else if self.beats[player.0][oponent.0] {
+            GameResult::Win
+        } else {
+            GameResult::Lose
+        }
+    }
+
+    /// The points of a shape, from 1 for the first one.
+    pub fn shape_points(&self, shape: Shape) -> u32 {
+        shape.0 as u32 + 1
+    }
+
+    /// The points of the result of a game.
+    pub fn outcome_points(&self, result: GameResult) -> u32 {
+        match result {
+            GameResult::Win => 6,
+            GameResult::Draw => 3,
+            GameResult::Lose => 0,
+        }
+    }
+
+    /// The points of the player for a game: those of the shape played and of the result.
+    pub fn score(&self, player: Shape, oponent: Shape) -> u32 {
+        self.shape_points(player) + self.outcome_points(self.play(player, oponent))
+    }
+
+    /// The shape to play against `oponent` to get `result`. When several shapes do, the
+    /// one worth the most points is chosen.
+    pub fn shape_for_result(&self, oponent: Shape, result: GameResult) -> Shape {
+        self.shapes()
+            .filter(|&shape| self.play(shape, oponent) == result)
+            .max_by_key(|&shape| self.shape_points(shape))
+            .expect("balanced games have a shape for every result")
+    }
+
+    /// Reads a line of a strategy guide like "A Y" as a `(player, oponent)` game.
+    pub fn decode_game(&self, line: &str, column: Column) -> Result<(Shape, Shape)> {
+        let (oponent_letter, letter) = split_columns(line)?;
+        let oponent = self.oponent_shape(oponent_letter).ok_or_else(|| {
+            Error::parse(
+                2,
+                column_of(line, oponent_letter),
+                oponent_letter,
+                "unknown shape",
+            )
+        })?;
+        let player = match column {
+            Column::Shape => self.player_shape(letter),
+            Column::Outcome => {
+                Rules::result(letter).map(|result| self.shape_for_result(oponent, result))
+            }
+        };
+        let player = player.ok_or_else(|| {
+            let reason = match column {
+                Column::Shape => "unknown shape",
+                Column::Outcome => "unknown outcome",
+            };
+            Error::parse(2, column_of(line, letter), letter, reason)
+        })?;
+        Ok((player, oponent))
+    }
+
+    /// The score of a strategy guide, whose second column holds `column`.
+    pub fn score_guide(&self, reader: impl BufRead, column: Column) -> Result<u64> {
+        let mut total = 0;
+        for (index, line) in reader.lines().enumerate() {
+            let (player, oponent) = self
+                .decode_game(&line?, column)
+                .map_err(|err| err.on_line(index + 1))?;
+            total += u64::from(self.score(player, oponent));
+        }
+        Ok(total)
+    }
+}
+
+/// The position in the alphabet of a single uppercase letter, from 0 for A.
+fn letter_index(letter: &str) -> Option<usize> {
+    match letter.as_bytes() {
+        &[byte] if byte.is_ascii_uppercase() => Some((byte - b'A') as usize),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day2::rules::{Column, Rules, Shape};
+    use crate::day2::GameResult;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn can_play_rock_paper_scissors_lizard_spock() {
+        let rules = Rules::rpsls();
+        let (rock, lizard, spock) = (Shape(0), Shape(3), Shape(4));
+
+        assert_eq!(GameResult::Win, rules.play(spock, rock));
+        assert_eq!(GameResult::Lose, rules.play(lizard, rock));
+        assert_eq!(GameResult::Draw, rules.play(lizard, lizard));
+        assert_eq!(5 + 6, rules.score(spock, rock));
+        assert_eq!(Some(spock), rules.shape("spock"));
+
+        // paper and Spock both beat rock, and Spock is worth more.
+        assert_eq!(spock, rules.shape_for_result(rock, GameResult::Win));
+    }
+
+    #[test]
+    fn can_build_cyclic_games() {
+        let classic = Rules::cyclic(names(&["Rock", "Scissors", "Paper"])).unwrap();
+        let (rock, scissors, paper) = (Shape(0), Shape(1), Shape(2));
+        assert_eq!(GameResult::Win, classic.play(rock, scissors));
+        assert_eq!(GameResult::Win, classic.play(paper, rock));
+        assert_eq!(GameResult::Lose, classic.play(scissors, rock));
+
+        let seven = Rules::cyclic(names(&["a", "b", "c", "d", "e", "f", "g"])).unwrap();
+        for shape in seven.shapes() {
+            let wins = seven
+                .shapes()
+                .filter(|&other| seven.play(shape, other) == GameResult::Win)
+                .count();
+            assert_eq!(3, wins);
+        }
+    }
+
+    #[test]
+    fn reports_unbalanced_games() {
+        let err = Rules::cyclic(names(&["a", "b", "c", "d"])).unwrap_err();
+        assert_eq!(
+            "a balanced game needs an odd number of shapes, at least 3, not 4",
+            err.to_string()
+        );
+
+        let err = Rules::new(names(&["a", "b", "c"]), &[(0, 1), (0, 2), (1, 2)]).unwrap_err();
+        assert_eq!("a beats 2 shapes instead of 1", err.to_string());
+    }
+
+    #[test]
+    fn can_score_guides_with_the_rules() {
+        let guide = "A Y\nB X\nC Z";
+        let classic = Rules::classic();
+        assert_eq!(
+            15,
+            classic
+                .score_guide(guide.as_bytes(), Column::Shape)
+                .unwrap()
+        );
+        assert_eq!(
+            12,
+            classic
+                .score_guide(guide.as_bytes(), Column::Outcome)
+                .unwrap()
+        );
+
+        // Spock against rock, scissors against scissors, lizard against Spock.
+        let guide = "A Z\nC X\nE Y";
+        let rpsls = Rules::by_name("rpsls").unwrap();
+        assert_eq!(
+            (5 + 6) + (3 + 3) + (4 + 6),
+            rpsls.score_guide(guide.as_bytes(), Column::Shape).unwrap()
+        );
+        // Spock beats rock, lizard loses to scissors, and Spock draws.
+        assert_eq!(
+            (5 + 6) + 4 + (5 + 3),
+            rpsls
+                .score_guide(guide.as_bytes(), Column::Outcome)
+                .unwrap()
+        );
+
+        let err = rpsls
+            .score_guide("A V\nF X".as_bytes(), Column::Shape)
+            .unwrap_err();
+        assert_eq!(
+            "day 2, line 2, column 1: unknown shape: 'F'",
+            err.to_string()
+        );
+        let err = rpsls
+            .score_guide("A V".as_bytes(), Column::Outcome)
+            .unwrap_err();
+        assert_eq!(
+            "day 2, line 1, column 3: unknown outcome: 'V'",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Scoring rules of the strategy guide: the points of each outcome and shape, and what
+//! the letters of the guide stand for. They can be loaded from a TOML file:
+//!
+//! ```toml
+//! [outcomes]
+//! win = 6
+//! draw = 3
+//! lose = 0
+//!
+//! [shapes]
+//! rock = 1
+//! paper = 2
+//! scissors = 3
+//!
+//! # the shape played, in either column
+//! [letters]
+//! A = "rock"
+//! X = "rock"
+//!
+//! # the outcome asked for by the second column, in the second part
+//! [results]
+//! X = "lose"
+//! ```
+//!
+//! or from `key = value` lines, where each key starts with its section, like
+//! `outcomes.win = 6` or `letters.A = rock`. Whatever is left out keeps the rules of the
+//! puzzle.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::day2::rules::Rules;
+use crate::day2::{GameElement, GameResult};
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scoring {
+    win: u32,
+    draw: u32,
+    lose: u32,
+    /// The points of each shape, indexed by [`GameElement::shape`].
+    shapes: [u32; 3],
+    letters: BTreeMap<String, GameElement>,
+    results: BTreeMap<String, GameResult>,
+}
+
+/// A scoring file as written, before its names are checked.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ScoringFile {
+    outcomes: BTreeMap<String, u32>,
+    shapes: BTreeMap<String, u32>,
+    letters: BTreeMap<String, String>,
+    results: BTreeMap<String, String>,
+}
+
+fn parse_shape(name: &str) -> Result<GameElement> {
+    Rules::classic()
+        .shape(name)
+        .map(GameElement::from_shape)
+        .ok_or_else(|| Error::config(format!("unknown shape '{name}'")))
+}
+
+fn parse_result(name: &str) -> Result<GameResult> {
+    match name.to_ascii_lowercase().as_str() {
+        "win" => Ok(GameResult::Win),
+        "draw" => Ok(GameResult::Draw),
+        "lose" => Ok(GameResult::Lose),
+        _ => Err(Error::config(format!("unknown outcome '{name}'"))),
+    }
+}
+
+impl Scoring {
+    /// The rules of the puzzle.
+    pub fn classic() -> &'static Scoring {
+        static CLASSIC: OnceLock<Scoring> = OnceLock::new();
+        CLASSIC.get_or_init(|| {
+            let elements = [GameElement::Rock, GameElement::Paper, GameElement::Scissors];
+            let letters = ["A", "B", "C", "X", "Y", "Z"]
+                .into_iter()
+                .map(|letter| (letter.to_owned(), GameElement::parse(letter).unwrap()))
+                .collect();
+            let results = ["X", "Y", "Z"]
+                .into_iter()
+                .map(|letter| (letter.to_owned(), Rules::result(letter).unwrap()))
+                .collect();
+
+            Scoring {
+                win: GameResult::Win.points(),
+                draw: GameResult::Draw.points(),
+                lose: GameResult::Lose.points(),
+                shapes: elements.map(|element| element.score()),
+                letters,
+                results,
+            }
+        })
+    }
+
+    /// Loads a scoring file, read as TOML if its extension is `.toml`, and as `key = value`
+    /// lines otherwise.
+    pub fn load(path: &Path) -> Result<Scoring> {
+        let content = fs::read_to_string(path).map_err(|source| Error::Input {
+            path: path.to_owned(),
+            source,
+        })?;
+        let scoring = if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            Scoring::from_toml(&content)
+        } else {
+            Scoring::from_key_values(&content)
+        };
+        scoring.map_err(|err| err.in_file(path))
+    }
+
+    pub fn from_toml(content: &str) -> Result<Scoring> {
+        let file = toml::from_str(content).map_err(|err| Error::config(err.to_string()))?;
+        Scoring::from_file(file)
+    }
+
+    pub fn from_key_values(content: &str) -> Result<Scoring> {
+        let mut file = ScoringFile::default();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let entry = line.split_once('=').and_then(|(key, value)| {
+                let (section, name) = key.trim().split_once('.')?;
+                Some((section, name.to_owned(), value.trim().trim_matches('"')))
+            });
+            let Some((section, name, value)) = entry else {
+                return Err(
+                    Error::config(format!("expected 'section.key = value', got '{line}'"))
+                        .on_line(index + 1),
+                );
+            };
+
+            let points = || {
+                value.parse().map_err(|_| {
+                    Error::config(format!("invalid points '{value}'")).on_line(index + 1)
+                })
+            };
+            match section {
+                "outcomes" => {
+                    file.outcomes.insert(name, points()?);
+                }
+                "shapes" => {
+                    file.shapes.insert(name, points()?);
+                }
+                "letters" => {
+                    file.letters.insert(name, value.to_owned());
+                }
+                "results" => {
+                    file.results.insert(name, value.to_owned());
+                }
+                _ => {
+                    return Err(
+                        Error::config(format!("unknown section '{section}'")).on_line(index + 1)
+                    )
+                }
+            }
+        }
+
+        Scoring::from_file(file)
+    }
+
+    /// Applies a scoring file over the rules of the puzzle.
+    fn from_file(file: ScoringFile) -> Result<Scoring> {
+        let mut scoring = Scoring::classic().clone();
+        for (name, points) in file.outcomes {
+            match parse_result(&name)? {
+                GameResult::Win => scoring.win = points,
+                GameResult::Draw => scoring.draw = points,
+                GameResult::Lose => scoring.lose = points,
+            }
+        }
+        for (name, points) in file.shapes {
+            scoring.shapes[parse_shape(&name)?.shape().0] = points;
+        }
+        for (letter, name) in file.letters {
+            scoring.letters.insert(letter, parse_shape(&name)?);
+        }
+        for (letter, name) in file.results {
+            scoring.results.insert(letter, parse_result(&name)?);
+        }
+
+        // a game scores the points of a shape and of an outcome, which must fit together.
+        let shape = scoring.shapes.iter().max().copied().unwrap_or_default();
+        let outcome = scoring.win.max(scoring.draw).max(scoring.lose);
+        if shape.checked_add(outcome).is_none() {
+            return Err(Error::config(format!(
+                "{shape} points for a shape and {outcome} for an outcome add up to more than {}",
+                u32::MAX
+            )));
+        }
+
+        Ok(scoring)
+    }
+
+    /// The shape a letter of the guide stands for.
+    pub fn shape(&self, letter: &str) -> Option<GameElement> {
+        self.letters.get(letter).copied()
+    }
+
+    /// The outcome a letter of the second column asks for.
+    pub fn result(&self, letter: &str) -> Option<GameResult> {
+        self.results.get(letter).copied()
+    }
+
+    pub fn shape_points(&self, element: GameElement) -> u32 {
+        self.shapes[element.shape().0]
+    }
+
+    pub fn outcome_points(&self, result: GameResult) -> u32 {
+        match result {
+            GameResult::Win => self.win,
+            GameResult::Draw => self.draw,
+            GameResult::Lose => self.lose,
+        }
+    }
+
+    /// The score of a single game: the shape played, plus the points of its result.
+    pub fn score(&self, player: GameElement, oponent: GameElement) -> u32 {
+        self.shape_points(player) + self.outcome_points(player.play(oponent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use crate::day2::scoring::Scoring;
+    use crate::day2::{GameElement, GameResult, Tournament};
+    use crate::Error;
+
+    const GUIDE: &str = "A Y\nB X\nC Z";
+
+    #[test]
+    fn can_load_scoring_rules() {
+        let toml = "[outcomes]\nwin = 10\n\n[shapes]\nrock = 5\n\n[letters]\nY = \"scissors\"\n";
+        let scoring = Scoring::from_toml(toml).unwrap();
+        let key_values = "# a comment\noutcomes.win = 10\nshapes.Rock = 5\nletters.Y = scissors\n";
+        assert_eq!(scoring, Scoring::from_key_values(key_values).unwrap());
+
+        assert_eq!(10, scoring.outcome_points(GameResult::Win));
+        assert_eq!(3, scoring.outcome_points(GameResult::Draw));
+        assert_eq!(5, scoring.shape_points(GameElement::Rock));
+        assert_eq!(Some(GameElement::Scissors), scoring.shape("Y"));
+        assert_eq!(Some(GameResult::Draw), scoring.result("Y"));
+
+        // scissors against rock, rock against paper, scissors against scissors.
+        let tournament = Tournament::from_reader_with(GUIDE.as_bytes(), &scoring).unwrap();
+        assert_eq!(3 + 5 + (3 + 3), tournament.score_with(&scoring));
+
+        // rock against rock, rock against paper, rock against scissors.
+        let tournament = Tournament::from_reader_part2_with(GUIDE.as_bytes(), &scoring).unwrap();
+        assert_eq!((5 + 3) + 5 + (5 + 10), tournament.score_with(&scoring));
+    }
+
+    #[test]
+    fn reports_invalid_scoring_rules() {
+        let err = Scoring::from_key_values("outcomes.win = 6\nshapes.rock 1\n").unwrap_err();
+        assert_eq!(
+            "line 2: expected 'section.key = value', got 'shapes.rock 1'",
+            err.to_string()
+        );
+
+        let err = Scoring::from_key_values("shapes.rock = -1").unwrap_err();
+        assert_eq!("line 1: invalid points '-1'", err.to_string());
+
+        let err = Scoring::from_toml("[letters]\nX = \"lizard\"\n").unwrap_err();
+        assert_eq!("unknown shape 'lizard'", err.to_string());
+
+        let err = Scoring::from_key_values("results.X = tie").unwrap_err();
+        assert_eq!("unknown outcome 'tie'", err.to_string());
+
+        let err = Scoring::from_key_values("shapes.rock = 4294967295").unwrap_err();
+        assert_eq!(
+            "4294967295 points for a shape and 6 for an outcome add up to more than 4294967295",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn can_load_the_largest_points() {
+        let toml = "[outcomes]\nwin = 0\ndraw = 0\nlose = 0\n\n[shapes]\nrock = 4294967295\n";
+        let scoring = Scoring::from_toml(toml).unwrap();
+        assert_eq!(
+            u32::MAX,
+            scoring.score(GameElement::Rock, GameElement::Paper)
+        );
+
+        // rock is played in every game, and the total goes past the points of one game.
+        let tournament =
+            Tournament::from_reader_with("A X\nB X\nC X".as_bytes(), &scoring).unwrap();
+        assert_eq!(3 * u64::from(u32::MAX), tournament.score_with(&scoring));
+    }
+
+    #[test]
+    fn reports_the_scoring_file() {
+        let path = std::env::temp_dir().join("aoc2022-day2-scoring.txt");
+        fs::write(&path, "outcomes.win = 6\nshapes.rock = x\n").unwrap();
+        let err = Scoring::load(&path).unwrap_err();
+        assert_eq!(
+            format!("{}, line 2: invalid points 'x'", path.display()),
+            err.to_string()
+        );
+
+        let path = Path::new("missing-scoring.toml");
+        let err = Scoring::load(path).unwrap_err();
+        assert!(matches!(err, Error::Input { path: missing, .. } if missing == path));
+    }
+}
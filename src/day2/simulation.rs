@@ -0,0 +1,451 @@
+//! Matches of many rounds between players that adapt to their oponent, to compare
+//! strategies over more games than a strategy guide holds.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::day2::scoring::Scoring;
+use crate::day2::{GameElement, GameResult, Tournament};
+use crate::input::InputSource;
+use crate::{debug, Error, Result};
+
+/// A player of a match, which picks a shape every round and learns from the results.
+pub trait Player {
+    fn name(&self) -> String;
+
+    /// The shape to play in the next round.
+    fn choose(&mut self) -> GameElement;
+
+    /// What happened in the last round, from the point of view of the player.
+    fn observe(&mut self, mine: GameElement, theirs: GameElement, result: GameResult);
+}
+
+/// The shape that beats `element`.
+fn beat(element: GameElement) -> GameElement {
+    element.element_for_result(GameResult::Win)
+}
+
+/// The shape seen the most, the first one of [`GameElement::ALL`] on a tie.
+fn most_frequent(counts: &[u32; 3]) -> GameElement {
+    let (index, _) = counts
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|&(_, count)| count)
+        .expect("there are three shapes");
+    GameElement::ALL[index]
+}
+
+/// Plays what beats the shape the oponent played the most.
+#[derive(Debug, Default)]
+pub struct FrequencyCounter {
+    counts: [u32; 3],
+}
+
+impl Player for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency".to_owned()
+    }
+
+    fn choose(&mut self) -> GameElement {
+        beat(most_frequent(&self.counts))
+    }
+
+    fn observe(&mut self, _: GameElement, theirs: GameElement, _: GameResult) {
+        self.counts[theirs.shape().0] += 1;
+    }
+}
+
+/// Plays what beats the shape the oponent played the most after its last shape.
+#[derive(Debug, Default)]
+pub struct MarkovPredictor {
+    /// `transitions[a][b]` counts the times the oponent played `b` right after `a`.
+    transitions: [[u32; 3]; 3],
+    last: Option<GameElement>,
+}
+
+impl Player for MarkovPredictor {
+    fn name(&self) -> String {
+        "markov".to_owned()
+    }
+
+    fn choose(&mut self) -> GameElement {
+        match self.last {
+            Some(last) => beat(most_frequent(&self.transitions[last.shape().0])),
+            None => GameElement::Rock,
+        }
+    }
+
+    fn observe(&mut self, _: GameElement, theirs: GameElement, _: GameResult) {
+        if let Some(last) = self.last {
+            self.transitions[last.shape().0][theirs.shape().0] += 1;
+        }
+        self.last = Some(theirs);
+    }
+}
+
+/// Keeps its shape after a win, and otherwise plays what would have beaten the oponent.
+#[derive(Debug)]
+pub struct WinStayLoseShift {
+    next: GameElement,
+}
+
+impl Default for WinStayLoseShift {
+    fn default() -> WinStayLoseShift {
+        WinStayLoseShift {
+            next: GameElement::Rock,
+        }
+    }
+}
+
+impl Player for WinStayLoseShift {
+    fn name(&self) -> String {
+        "wsls".to_owned()
+    }
+
+    fn choose(&mut self) -> GameElement {
+        self.next
+    }
+
+    fn observe(&mut self, mine: GameElement, theirs: GameElement, result: GameResult) {
+        self.next = match result {
+            GameResult::Win => mine,
+            GameResult::Draw | GameResult::Lose => beat(theirs),
+        };
+    }
+}
+
+/// Replays the shapes of a strategy guide, starting over when it runs out.
+#[derive(Debug, Clone)]
+pub struct FixedGuide {
+    shapes: Vec<GameElement>,
+    round: usize,
+}
+
+impl FixedGuide {
+    pub fn new(shapes: Vec<GameElement>) -> FixedGuide {
+        FixedGuide { shapes, round: 0 }
+    }
+
+    /// The shapes played by a guide whose second column is the shape to play.
+    pub fn load(path: &Path) -> Result<FixedGuide> {
+        let reader = InputSource::File(path.to_owned()).open()?;
+        let tournament = Tournament::from_reader(reader).map_err(|err| err.in_file(path))?;
+        let shapes = tournament.0.iter().map(|&(player, _)| player).collect();
+        Ok(FixedGuide::new(shapes))
+    }
+}
+
+impl Player for FixedGuide {
+    fn name(&self) -> String {
+        "guide".to_owned()
+    }
+
+    fn choose(&mut self) -> GameElement {
+        if self.shapes.is_empty() {
+            return GameElement::Rock;
+        }
+        let shape = self.shapes[self.round % self.shapes.len()];
+        self.round += 1;
+        shape
+    }
+
+    fn observe(&mut self, _: GameElement, _: GameElement, _: GameResult) {}
+}
+
+/// A small SplitMix64 generator, so the random player is the same on every platform.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Plays a shape at random, the same ones for the same seed.
+#[derive(Debug, Clone)]
+pub struct RandomPlayer {
+    rng: Rng,
+}
+
+impl RandomPlayer {
+    pub fn new(seed: u64) -> RandomPlayer {
+        RandomPlayer { rng: Rng(seed) }
+    }
+}
+
+impl Player for RandomPlayer {
+    fn name(&self) -> String {
+        "random".to_owned()
+    }
+
+    fn choose(&mut self) -> GameElement {
+        GameElement::ALL[(self.rng.next() % 3) as usize]
+    }
+
+    fn observe(&mut self, _: GameElement, _: GameElement, _: GameResult) {}
+}
+
+/// A kind of player, which builds a new player for every match.
+#[derive(Debug, Clone)]
+pub enum PlayerSpec {
+    Frequency,
+    Markov,
+    WinStayLoseShift,
+    Random,
+    Guide(FixedGuide),
+}
+
+impl PlayerSpec {
+    /// Reads a kind of player from its name: `frequency`, `markov`, `wsls`, `random`,
+    /// or `guide=PATH` for a guide read from a file.
+    pub fn parse(spec: &str) -> Result<PlayerSpec> {
+        match spec {
+            "frequency" => Ok(PlayerSpec::Frequency),
+            "markov" => Ok(PlayerSpec::Markov),
+            "wsls" => Ok(PlayerSpec::WinStayLoseShift),
+            "random" => Ok(PlayerSpec::Random),
+            _ => match spec.strip_prefix("guide=") {
+                Some(path) => Ok(PlayerSpec::Guide(FixedGuide::load(Path::new(path))?)),
+                None => Err(Error::config(format!(
+                    "unknown player '{spec}', expected frequency, markov, wsls, random or \
+                     guide=PATH"
+                ))),
+            },
+        }
+    }
+
+    pub fn build(&self, seed: u64) -> Box<dyn Player> {
+        match self {
+            PlayerSpec::Frequency => Box::<FrequencyCounter>::default(),
+            PlayerSpec::Markov => Box::<MarkovPredictor>::default(),
+            PlayerSpec::WinStayLoseShift => Box::<WinStayLoseShift>::default(),
+            PlayerSpec::Random => Box::new(RandomPlayer::new(seed)),
+            PlayerSpec::Guide(guide) => Box::new(guide.clone()),
+        }
+    }
+}
+
+/// The results of one side over every match of a simulation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Side {
+    pub name: String,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// The score of every match.
+    pub scores: Vec<u64>,
+}
+
+impl Side {
+    fn rate(&self, count: usize) -> f64 {
+        let rounds = self.wins + self.draws + self.losses;
+        count as f64 / rounds.max(1) as f64
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins)
+    }
+
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        self.rate(self.losses)
+    }
+
+    pub fn mean_score(&self) -> f64 {
+        let total: u128 = self.scores.iter().map(|&score| u128::from(score)).sum();
+        total as f64 / self.scores.len().max(1) as f64
+    }
+
+    /// Population standard deviation of the scores of the matches.
+    pub fn stddev(&self) -> f64 {
+        let mean = self.mean_score();
+        let variance = self
+            .scores
+            .iter()
+            .map(|&score| (score as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.scores.len().max(1) as f64;
+        variance.sqrt()
+    }
+
+    /// The nearest-rank percentile of the scores of the matches.
+    pub fn percentile(&self, percentile: u8) -> u64 {
+        let mut scores = self.scores.clone();
+        scores.sort_unstable();
+        let rank = (percentile as usize * scores.len()).div_ceil(100).max(1);
+        scores.get(rank - 1).copied().unwrap_or(0)
+    }
+}
+
+/// The results of a simulation, from the point of view of both sides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub matches: usize,
+    pub rounds: usize,
+    pub sides: [Side; 2],
+}
+
+/// Runs `matches` matches of `rounds` rounds, with new players every match.
+#[derive(Debug, Clone, Copy)]
+pub struct Simulation {
+    pub matches: usize,
+    pub rounds: usize,
+    /// The seed of the first match. Every match and side gets its own seed from it.
+    pub seed: u64,
+}
+
+impl Simulation {
+    pub fn run(&self, first: &PlayerSpec, second: &PlayerSpec, scoring: &Scoring) -> Report {
+        let mut seeds = Rng(self.seed);
+        let mut sides: [Side; 2] = Default::default();
+
+        for _ in 0..self.matches {
+            let mut players = [first.build(seeds.next()), second.build(seeds.next())];
+            let mut scores = [0u64, 0];
+
+            for _ in 0..self.rounds {
+                let shapes = [players[0].choose(), players[1].choose()];
+                for (side, player) in players.iter_mut().enumerate() {
+                    let (mine, theirs) = (shapes[side], shapes[1 - side]);
+                    let result = mine.play(theirs);
+                    match result {
+                        GameResult::Win => sides[side].wins += 1,
+                        GameResult::Draw => sides[side].draws += 1,
+                        GameResult::Lose => sides[side].losses += 1,
+                    }
+                    scores[side] += u64::from(scoring.score(mine, theirs));
+                    player.observe(mine, theirs, result);
+                }
+            }
+
+            for (side, score) in sides.iter_mut().zip(scores) {
+                side.scores.push(score);
+            }
+            debug!(
+                "{} {} - {} {}",
+                players[0].name(),
+                scores[0],
+                scores[1],
+                players[1].name()
+            );
+            sides[0].name = players[0].name();
+            sides[1].name = players[1].name();
+        }
+
+        Report {
+            matches: self.matches,
+            rounds: self.rounds,
+            sides,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Matches: {} of {} rounds", self.matches, self.rounds)?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<10} | {:>6} | {:>6} | {:>6} | {:>9} | {:>8} | {:>7} | {:>7} | {:>7}",
+            "Player", "Win", "Draw", "Loss", "Mean", "Std dev", "Min", "Median", "Max"
+        )?;
+        writeln!(
+            f,
+            "-----------+--------+--------+--------+-----------+----------+---------+---------+--------"
+        )?;
+        for side in &self.sides {
+            writeln!(
+                f,
+                "{:<10} | {:>5.1}% | {:>5.1}% | {:>5.1}% | {:>9.1} | {:>8.1} | {:>7} | {:>7} | {:>7}",
+                side.name,
+                side.win_rate() * 100.0,
+                side.draw_rate() * 100.0,
+                side.loss_rate() * 100.0,
+                side.mean_score(),
+                side.stddev(),
+                side.percentile(0),
+                side.percentile(50),
+                side.percentile(100),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::day2::scoring::Scoring;
+    use crate::day2::simulation::{FixedGuide, PlayerSpec, Simulation};
+    use crate::day2::GameElement::{Paper, Rock, Scissors};
+    use crate::Error;
+
+    fn simulate(first: &PlayerSpec, second: &PlayerSpec, rounds: usize) -> [usize; 3] {
+        let simulation = Simulation {
+            matches: 1,
+            rounds,
+            seed: 7,
+        };
+        let report = simulation.run(first, second, Scoring::classic());
+        let side = &report.sides[0];
+        [side.wins, side.draws, side.losses]
+    }
+
+    #[test]
+    fn can_adapt_to_the_oponent() {
+        let rocks = PlayerSpec::Guide(FixedGuide::new(vec![Rock]));
+        assert_eq!([10, 0, 0], simulate(&PlayerSpec::Frequency, &rocks, 10));
+        assert_eq!(
+            [9, 1, 0],
+            simulate(&PlayerSpec::WinStayLoseShift, &rocks, 10)
+        );
+
+        // the cycle is learned after a couple of rounds.
+        let cycle = PlayerSpec::Guide(FixedGuide::new(vec![Rock, Paper, Scissors]));
+        let [wins, _, _] = simulate(&PlayerSpec::Markov, &cycle, 100);
+        assert!(wins >= 95, "{wins}");
+    }
+
+    #[test]
+    fn can_replay_random_matches() {
+        let simulation = Simulation {
+            matches: 20,
+            rounds: 300,
+            seed: 42,
+        };
+        let report = simulation.run(&PlayerSpec::Random, &PlayerSpec::Markov, Scoring::classic());
+        assert_eq!(
+            report,
+            simulation.run(&PlayerSpec::Random, &PlayerSpec::Markov, Scoring::classic())
+        );
+
+        let [random, markov] = &report.sides;
+        assert_eq!(20, random.scores.len());
+        assert_eq!(random.wins, markov.losses);
+        assert_eq!(20 * 300, random.wins + random.draws + random.losses);
+        // nothing can be learned from a random player.
+        assert!((random.win_rate() - 1.0 / 3.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn reports_invalid_players() {
+        let err = PlayerSpec::parse("bogus").unwrap_err();
+        assert_eq!(
+            "unknown player 'bogus', expected frequency, markov, wsls, random or guide=PATH",
+            err.to_string()
+        );
+
+        let err = PlayerSpec::parse("guide=missing-guide.txt").unwrap_err();
+        assert!(matches!(err, Error::Input { path, .. } if path == Path::new("missing-guide.txt")));
+    }
+}
@@ -0,0 +1,318 @@
+use std::io::BufRead;
+
+use crate::{debug, Error, Result, Solution};
+
+/// A set of items, with the bit of each item at the position of its priority, from 1
+/// for `a` to 52 for `Z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Items(u64);
+
+impl Items {
+    /// The ascii letters of a string, leaving anything else out.
+    fn letters(items: &str) -> Items {
+        let bits = items
+            .chars()
+            .filter(char::is_ascii_alphabetic)
+            .fold(0, |bits, item| bits | 1 << Rucksack::priority(item));
+        Items(bits)
+    }
+
+    fn intersection(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// The priorities of the items, from the lowest one.
+    fn priorities(self) -> impl Iterator<Item = u32> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            let priority = bits.trailing_zeros();
+            bits &= bits.checked_sub(1)?;
+            Some(priority)
+        })
+    }
+
+    fn total_priority(self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+/// The item of a priority, the reverse of [`Rucksack::priority`].
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+#[derive(Debug)]
+pub struct Rucksack {
+    first: Items,
+    second: Items,
+}
+
+impl Rucksack {
+    /// Splits the items of a line in two compartments, which must have the same size.
+    /// Like groups, rucksacks only keep the ascii letters of the line.
+    pub fn new(input_line: &str) -> Option<Rucksack> {
+        if input_line.is_empty() || !input_line.len().is_multiple_of(2) {
+            return None;
+        }
+
+        let (first, second) = input_line.split_at_checked(input_line.len() / 2)?;
+        Some(Rucksack {
+            first: Items::letters(first),
+            second: Items::letters(second),
+        })
+    }
+
+    /// The items found in both compartments, from the lowest priority.
+    pub fn find_wrong_items(&self) -> Vec<char> {
+        let wrong = self.first.intersection(self.second);
+        let mut items = Vec::with_capacity(wrong.len());
+        items.extend(wrong.priorities().map(item));
+        items
+    }
+
+    pub fn priority(c: char) -> u32 {
+        if c.is_lowercase() {
+            (c as u32) - 96
+        } else {
+            (c as u32) - 38
+        }
+    }
+
+    pub fn get_priorities(&self) -> u32 {
+        self.first.intersection(self.second).total_priority()
+    }
+}
+
+#[derive(Debug)]
+pub struct Group {
+    first: Items,
+    second: Items,
+    third: Items,
+}
+
+impl Group {
+    /// Gathers the items carried by each of the three elves of a group. Anything but
+    /// ascii letters is left out, as it cannot be an item.
+    pub fn new(first: &str, second: &str, third: &str) -> Group {
+        Group {
+            first: Items::letters(first),
+            second: Items::letters(second),
+            third: Items::letters(third),
+        }
+    }
+
+    pub fn priority(&self) -> u32 {
+        self.first
+            .intersection(self.second)
+            .intersection(self.third)
+            .total_priority()
+    }
+}
+
+/// Checks that a line only holds items, which are identified by ascii letters.
+fn check_items(line: &str) -> Result<()> {
+    match line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        Some((offset, c)) => Err(Error::parse(3, offset + 1, &c.to_string(), "invalid item")),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug)]
+pub struct Badges(pub Vec<Group>);
+
+impl Badges {
+    pub fn new(reader: impl BufRead) -> Result<Badges> {
+        let mut groups = vec![];
+
+        let mut lines = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            check_items(&line).map_err(|err| err.on_line(index + 1))?;
+            lines.push(line);
+        }
+        let chunks = lines.chunks_exact(3);
+        if let Some(line) = chunks.remainder().first() {
+            let line_number = lines.len() - chunks.remainder().len() + 1;
+            return Err(
+                Error::parse(3, 1, line, "incomplete group of three elves").on_line(line_number)
+            );
+        }
+
+        for chunk in chunks {
+            groups.push(Group::new(&chunk[0], &chunk[1], &chunk[2]));
+        }
+        debug!("read {} groups of three elves", groups.len());
+        Ok(Badges(groups))
+    }
+
+    pub fn total(&self) -> u64 {
+        let mut total = 0;
+
+        for group in &self.0 {
+            total += u64::from(group.priority());
+        }
+
+        total
+    }
+}
+
+/// Reads a line holding the items of a rucksack.
+fn parse_rucksack(line: &str) -> Result<Rucksack> {
+    check_items(line)?;
+    Rucksack::new(line)
+        .ok_or_else(|| Error::parse(3, 1, line, "expected two compartments of the same size"))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = (Vec<Rucksack>, Badges);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(mut reader: impl BufRead) -> Result<Self::Input> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let mut rucksacks = vec![];
+        for (index, line) in content.lines().enumerate() {
+            let rucksack = parse_rucksack(line).map_err(|err| err.on_line(index + 1))?;
+            rucksacks.push(rucksack);
+        }
+        let badges = Badges::new(content.as_bytes())?;
+
+        Ok((rucksacks, badges))
+    }
+
+    fn part1(input: &Self::Input) -> u64 {
+        input
+            .0
+            .iter()
+            .map(|rucksack| u64::from(rucksack.get_priorities()))
+            .sum()
+    }
+
+    fn part2(input: &Self::Input) -> u64 {
+        input.1.total()
+    }
+
+    /// Only keeps the lines of the group being read.
+    fn stream(reader: impl BufRead) -> Result<(u64, u64)> {
+        let (mut priorities, mut badges) = (0, 0);
+        let mut group: Vec<String> = Vec::with_capacity(3);
+        let mut line_number = 0;
+        for line in reader.lines() {
+            let line = line?;
+            line_number += 1;
+            let rucksack = parse_rucksack(&line).map_err(|err| err.on_line(line_number))?;
+            priorities += u64::from(rucksack.get_priorities());
+
+            group.push(line);
+            if let [first, second, third] = &group[..] {
+                badges += u64::from(Group::new(first, second, third).priority());
+                group.clear();
+            }
+        }
+
+        if let Some(line) = group.first() {
+            let line_number = line_number - group.len() + 1;
+            return Err(
+                Error::parse(3, 1, line, "incomplete group of three elves").on_line(line_number)
+            );
+        }
+
+        Ok((priorities, badges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day3::{Day3, Group, Rucksack};
+    use crate::Solution;
+
+    #[test]
+    fn can_find_wrong_items() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let rucksack = Rucksack::new(input).unwrap();
+        assert_eq!(vec!['p'], rucksack.find_wrong_items());
+
+        let input = "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL";
+        let rucksack = Rucksack::new(input).unwrap();
+        assert_eq!(vec!['L'], rucksack.find_wrong_items());
+
+        let input = "PmmdzqPrVvPwwTWBwg";
+        let rucksack = Rucksack::new(input).unwrap();
+        assert_eq!(vec!['P'], rucksack.find_wrong_items());
+
+        let input = "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn";
+        let rucksack = Rucksack::new(input).unwrap();
+        assert_eq!(vec!['v'], rucksack.find_wrong_items());
+
+        let input = "ttgJtRGJQctTZtZT";
+        let rucksack = Rucksack::new(input).unwrap();
+        assert_eq!(vec!['t'], rucksack.find_wrong_items());
+
+        let input = "CrZsJsPPZsGzwwsLwLmpwMDw";
+        let rucksack = Rucksack::new(input).unwrap();
+        assert_eq!(vec!['s'], rucksack.find_wrong_items());
+    }
+
+    #[test]
+    fn can_find_badges() {
+        let group = Group::new(
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+        );
+        assert_eq!(Rucksack::priority('r'), group.priority());
+
+        let group = Group::new(
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        );
+        assert_eq!(52, group.priority());
+
+        // items found in both compartments come from the lowest priority.
+        let rucksack = Rucksack::new("aZbzZazb").unwrap();
+        assert_eq!(vec!['a', 'b', 'z', 'Z'], rucksack.find_wrong_items());
+        assert_eq!(1 + 2 + 26 + 52, rucksack.get_priorities());
+    }
+
+    #[test]
+    fn can_leave_out_anything_but_items() {
+        // rucksacks and groups both skip what is not an ascii letter.
+        let rucksack = Rucksack::new("a-b?b-a?").unwrap();
+        assert_eq!(vec!['a', 'b'], rucksack.find_wrong_items());
+        let group = Group::new("a-b?", "b-a?", "?ab-");
+        assert_eq!(rucksack.get_priorities(), group.priority());
+
+        // the middle of the line falls inside a character.
+        assert!(Rucksack::new("aéa").is_none());
+    }
+
+    #[test]
+    fn reports_invalid_rucksacks() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSLx\n";
+        let err = Day3::parse(input.as_bytes()).unwrap_err();
+        assert_eq!(
+            "day 3, line 2, column 1: expected two compartments of the same size: 'jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSLx'",
+            err.to_string()
+        );
+
+        let err = Day3::parse("vJrw-WtwJgWr\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            "day 3, line 1, column 5: invalid item: '-'",
+            err.to_string()
+        );
+    }
+}
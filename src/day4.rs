@@ -0,0 +1,174 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::error::column_of;
+use crate::{debug, Error, Result, Solution};
+
+#[derive(Debug, PartialEq)]
+pub struct AssignmentPair(pub RangeInclusive<u32>, pub RangeInclusive<u32>);
+
+impl AssignmentPair {
+    pub fn is_fully_contained(&self) -> bool {
+        let (a, b) = (&self.0, &self.1);
+
+        (a.start() <= b.start() && b.end() <= a.end())
+            || (b.start() <= a.start() && a.end() <= b.end())
+    }
+
+    pub fn has_overlap(&self) -> bool {
+        let (a, b) = (&self.0, &self.1);
+
+        a.start() <= b.end() && b.start() <= a.end()
+    }
+}
+
+/// Parses one of the section ids of an assignment.
+fn parse_section(line: &str, section: &str) -> Result<u32> {
+    section
+        .parse()
+        .map_err(|_| Error::parse(4, column_of(line, section), section, "invalid section id"))
+}
+
+impl FromStr for AssignmentPair {
+    type Err = Error;
+
+    /// Parses a &str like "2-4,6-8"
+    fn from_str(s: &str) -> Result<Self> {
+        let mut splitted_text = s.split(",");
+        if let (Some(fst), Some(snd)) = (splitted_text.next(), splitted_text.next()) {
+            let mut splitted_fst = fst.split("-");
+            let mut splitted_snd = snd.split("-");
+
+            if let (Some(x1), Some(y1), Some(x2), Some(y2)) = (
+                splitted_fst.next(),
+                splitted_fst.next(),
+                splitted_snd.next(),
+                splitted_snd.next(),
+            ) {
+                let x1_u32 = parse_section(s, x1)?;
+                let y1_u32 = parse_section(s, y1)?;
+                let x2_u32 = parse_section(s, x2)?;
+                let y2_u32 = parse_section(s, y2)?;
+
+                return Ok(AssignmentPair(
+                    RangeInclusive::new(x1_u32, y1_u32),
+                    RangeInclusive::new(x2_u32, y2_u32),
+                ));
+            }
+        }
+
+        Err(Error::parse(
+            4,
+            1,
+            s,
+            "expected two assignments like 2-4,6-8",
+        ))
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<AssignmentPair>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        let mut assignment_pairs = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let assignment_pair = line?
+                .parse::<AssignmentPair>()
+                .map_err(|err| err.on_line(index + 1))?;
+            assignment_pairs.push(assignment_pair);
+        }
+
+        debug!("read {} assignment pairs", assignment_pairs.len());
+        Ok(assignment_pairs)
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        input
+            .iter()
+            .filter(|assignment_pair| assignment_pair.is_fully_contained())
+            .count()
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        input
+            .iter()
+            .filter(|assignment_pair| assignment_pair.has_overlap())
+            .count()
+    }
+
+    fn stream(reader: impl BufRead) -> Result<(usize, usize)> {
+        let (mut contained, mut overlapping) = (0, 0);
+        for (index, line) in reader.lines().enumerate() {
+            let assignment_pair = line?
+                .parse::<AssignmentPair>()
+                .map_err(|err| err.on_line(index + 1))?;
+            contained += usize::from(assignment_pair.is_fully_contained());
+            overlapping += usize::from(assignment_pair.has_overlap());
+        }
+
+        Ok((contained, overlapping))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use crate::day4::AssignmentPair;
+
+    #[test]
+    fn can_parse_assignment_pairs() {
+        let input = "2-4,6-8";
+
+        let expected = AssignmentPair(RangeInclusive::new(2, 4), RangeInclusive::new(6, 8));
+
+        assert_eq!(expected, input.parse().unwrap());
+    }
+
+    #[test]
+    fn reports_invalid_assignment_pairs() {
+        let err = "2-4,6-x".parse::<AssignmentPair>().unwrap_err();
+        assert_eq!(
+            "day 4, line 1, column 7: invalid section id: 'x'",
+            err.to_string()
+        );
+
+        let err = "2-4".parse::<AssignmentPair>().unwrap_err();
+        assert_eq!(
+            "day 4, line 1, column 1: expected two assignments like 2-4,6-8: '2-4'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn can_check_containment() {
+        // the assignments are not contained.
+        let input = "2-4,6-8";
+        let assignment_pair: AssignmentPair = input.parse().unwrap();
+        assert!(!assignment_pair.is_fully_contained());
+
+        // the second assignment is fully contained in the first one.
+        let input = "2-8,3-7";
+        let assignment_pair: AssignmentPair = input.parse().unwrap();
+        assert!(assignment_pair.is_fully_contained());
+    }
+
+    #[test]
+    fn can_check_wide_assignments() {
+        // the sections are compared by their bounds, however many there are.
+        let assignment_pair: AssignmentPair = "1-4000000000,2-3".parse().unwrap();
+        assert!(assignment_pair.is_fully_contained());
+        assert!(assignment_pair.has_overlap());
+
+        let assignment_pair: AssignmentPair = "5-4000000000,1-4".parse().unwrap();
+        assert!(!assignment_pair.is_fully_contained());
+        assert!(!assignment_pair.has_overlap());
+    }
+}
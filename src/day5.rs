@@ -0,0 +1,434 @@
+use itertools::Itertools;
+use pest::Parser;
+use pest_derive::Parser;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{debug, trace, Error, Result, Solution};
+
+#[derive(Parser)]
+#[grammar = "grammars/day5.pest"]
+pub struct Day5Parser;
+
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct State {
+    pub stacks: HashMap<usize, Line>,
+}
+
+impl State {
+    pub fn apply(&mut self, instruction: &Move) {
+        let amount = instruction.amount;
+        let mut moved_crates = vec![];
+        self.stacks
+            .entry(instruction.from - 1)
+            .and_modify(|from_line| {
+                moved_crates = from_line.remove_crates(amount);
+            });
+
+        self.stacks
+            .entry(instruction.to - 1)
+            .and_modify(|to_line| to_line.add_crates(moved_crates));
+        trace!("{instruction}, one crate at a time: {self}");
+    }
+
+    pub fn apply2(&mut self, instruction: &Move) {
+        let amount = instruction.amount;
+        let mut moved_crates = vec![];
+        self.stacks
+            .entry(instruction.from - 1)
+            .and_modify(|from_line| {
+                moved_crates = from_line.remove_crates(amount);
+            });
+
+        self.stacks
+            .entry(instruction.to - 1)
+            .and_modify(|to_line| to_line.add_crates2(moved_crates));
+        trace!("{instruction}, all crates at once: {self}");
+    }
+
+    pub fn from_pair(pair: pest::iterators::Pair<Rule>) -> Self {
+        let mut stacks: HashMap<usize, Line> = HashMap::new();
+        for elem in pair.into_inner() {
+            // parsing each line
+            match elem.as_rule() {
+                // parsing a single line
+                Rule::line => {
+                    for (index, inner_elem) in elem.into_inner().enumerate() {
+                        // populate the lines for each column.
+                        stacks.entry(index).or_default();
+                        let item = inner_elem.into_inner().next().unwrap();
+                        match item.as_rule() {
+                            Rule::empty_crate => {
+                                stacks.entry(index).and_modify(|line| line.add(None));
+                            }
+                            Rule::full_crate => {
+                                let letter = item.into_inner().next().unwrap().as_str().to_owned();
+                                stacks
+                                    .entry(index)
+                                    .and_modify(|line| line.add(Some(letter)));
+                            }
+                            _ => unreachable!(),
+                        }
+                    }
+                }
+                Rule::column_numbers => {}
+                _ => unreachable!(),
+            }
+        }
+        State { stacks }
+    }
+
+    /// The number of crates of each stack, from the first stack to the last.
+    pub fn heights(&self) -> Vec<usize> {
+        (0..self.stacks.len())
+            .map(|n| self.stacks.get(&n).map_or(0, |line| line.0.len()))
+            .collect()
+    }
+
+    /// The crates at the top of each stack, from the first stack to the last.
+    pub fn last_crates(&self) -> String {
+        let mut crates = String::new();
+
+        let stack_numbers = self.stacks.keys().sorted();
+
+        for n in stack_numbers {
+            let line = self.stacks.get(n).unwrap();
+            if let Some(character) = line.get_last_crate() {
+                crates.push_str(&character)
+            }
+        }
+
+        crates
+    }
+}
+
+/// Writes the stacks from the first to the last, each from its bottom crate to its top
+/// one, like `ZN MCD P`.
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stacks = self.stacks.keys().sorted().map(|n| {
+            let line = &self.stacks[n];
+            line.0.iter().rev().flatten().join("")
+        });
+        write!(f, "{}", stacks.format(" "))
+    }
+}
+
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Line(pub Vec<Option<String>>);
+
+impl Line {
+    pub fn get_last_crate(&self) -> Option<String> {
+        self.0
+            .clone()
+            .into_iter()
+            .find(|item| item.is_some())
+            .flatten()
+    }
+    pub fn add(&mut self, item: Option<String>) {
+        if item.is_some() {
+            self.0.push(item)
+        }
+    }
+
+    pub fn add_crates(&mut self, items: Vec<Option<String>>) {
+        for item in items {
+            self.0.insert(0, item)
+        }
+    }
+
+    pub fn add_crates2(&mut self, items: Vec<Option<String>>) {
+        let mut new_items = items.clone();
+        let mut old_items = self.0.clone();
+        new_items.append(&mut old_items);
+        self.0 = new_items;
+    }
+
+    pub fn remove_crates(&mut self, amount: usize) -> Vec<Option<String>> {
+        let removed = self.0.drain(0..amount).collect();
+        removed
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Move {
+    pub from: usize,
+    pub to: usize,
+    pub amount: usize,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.amount, self.from, self.to)
+    }
+}
+
+impl Move {
+    /// Reads an instruction, whose moves can only refer to the stacks of the drawing and
+    /// take at most the crates of their stack. `heights` holds the number of crates of
+    /// each stack before the move, and is updated to the numbers after it.
+    pub fn from_pair(pair: pest::iterators::Pair<Rule>, heights: &mut [usize]) -> Result<Move> {
+        for stack in pair.clone().into_inner().skip(1) {
+            let number = parse_number(&stack)?;
+            if !(1..=heights.len()).contains(&number) {
+                return Err(error_at(&stack, "unknown stack"));
+            }
+        }
+
+        let mut members = pair.into_inner();
+        match (members.next(), members.next(), members.next()) {
+            (Some(amount_pair), Some(from), Some(to)) => {
+                let instruction = Move {
+                    from: parse_number(&from)?,
+                    to: parse_number(&to)?,
+                    amount: parse_number(&amount_pair)?,
+                };
+                if instruction.amount > heights[instruction.from - 1] {
+                    return Err(error_at(&amount_pair, "not enough crates in the stack"));
+                }
+                heights[instruction.from - 1] -= instruction.amount;
+                heights[instruction.to - 1] += instruction.amount;
+                Ok(instruction)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Program(pub Vec<Move>);
+
+impl Program {
+    pub fn from_pair(pair: pest::iterators::Pair<Rule>, state: &State) -> Result<Program> {
+        let mut heights = state.heights();
+        let mut moves: Vec<Move> = vec![];
+        for elem in pair.into_inner() {
+            moves.push(Move::from_pair(elem, &mut heights)?);
+        }
+
+        Ok(Program(moves))
+    }
+}
+
+/// Parses a number matched by the grammar, reporting where it is when it does not fit.
+fn parse_number(pair: &pest::iterators::Pair<Rule>) -> Result<usize> {
+    pair.as_str()
+        .parse()
+        .map_err(|_| error_at(pair, "invalid number"))
+}
+
+fn error_at(pair: &pest::iterators::Pair<Rule>, reason: &str) -> Error {
+    let (line, column) = pair.as_span().start_pos().line_col();
+    Error::parse(5, column, pair.as_str(), reason).on_line(line)
+}
+
+fn from_pest(err: pest::error::Error<Rule>, input: &str) -> Error {
+    let (line, column) = match err.line_col {
+        pest::error::LineColLocation::Pos(position) => position,
+        pest::error::LineColLocation::Span(start, _) => start,
+    };
+    let text = input.lines().nth(line - 1).unwrap_or_default();
+    Error::parse(5, column, text, err.variant.message()).on_line(line)
+}
+
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Problem {
+    pub state: State,
+    pub moves: Program,
+}
+
+impl Problem {
+    pub fn run(&mut self) -> String {
+        for instruction in &self.moves.0 {
+            self.state.apply(instruction);
+        }
+
+        self.last_crates()
+    }
+
+    pub fn run2(&mut self) -> String {
+        for instruction in &self.moves.0 {
+            self.state.apply2(instruction);
+        }
+
+        self.last_crates()
+    }
+
+    pub fn last_crates(&self) -> String {
+        self.state.last_crates()
+    }
+}
+
+impl FromStr for Problem {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parsed_program =
+            Day5Parser::parse(Rule::input, s).map_err(|err| from_pest(err, s))?;
+        let mut problem = Problem::default();
+        if let Some(parsed_input) = parsed_program.next() {
+            for elem in parsed_input.into_inner() {
+                match elem.as_rule() {
+                    Rule::state => {
+                        let state = State::from_pair(elem);
+                        debug!("read {} stacks: {state}", state.stacks.len());
+                        problem.state = state;
+                    }
+
+                    Rule::program => {
+                        let program = Program::from_pair(elem, &problem.state)?;
+                        debug!("read {} moves", program.0.len());
+                        problem.moves = program;
+                    }
+                    Rule::EOI => {}
+                    _ => unreachable!(),
+                }
+            }
+        }
+        Ok(problem)
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Problem;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(mut reader: impl BufRead) -> Result<Self::Input> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        input.clone().run()
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        input.clone().run2()
+    }
+
+    /// Reads the drawing, then applies each move as soon as it is read, to a state for
+    /// each crane.
+    fn stream(reader: impl BufRead) -> Result<(String, String)> {
+        let mut lines = reader.lines().enumerate();
+
+        let mut drawing = String::new();
+        for (_, line) in lines.by_ref() {
+            let line = line?;
+            if line.is_empty() {
+                break;
+            }
+            if !drawing.is_empty() {
+                drawing.push('\n');
+            }
+            drawing.push_str(&line);
+        }
+        let mut state = Day5Parser::parse(Rule::drawing, &drawing)
+            .map_err(|err| from_pest(err, &drawing))?
+            .next()
+            .and_then(|pair| pair.into_inner().next())
+            .map(State::from_pair)
+            .unwrap_or_default();
+        debug!("read {} stacks: {state}", state.stacks.len());
+        let mut state2 = state.clone();
+        let mut heights = state.heights();
+
+        let mut blank_line = None;
+        for (index, line) in lines {
+            let line = line?;
+            if line.is_empty() {
+                blank_line.get_or_insert(index + 1);
+                continue;
+            }
+            if let Some(line_number) = blank_line {
+                return Err(Error::parse(5, 1, "", "expected end of input").on_line(line_number));
+            }
+
+            let instruction = Day5Parser::parse(Rule::move_line, &line)
+                .map_err(|err| from_pest(err, &line))
+                .and_then(|mut pairs| {
+                    let pair = pairs.next().and_then(|pair| pair.into_inner().next());
+                    Move::from_pair(pair.unwrap(), &mut heights)
+                })
+                .map_err(|err| err.on_line(index + 1))?;
+            state.apply(&instruction);
+            state2.apply2(&instruction);
+        }
+
+        Ok((state.last_crates(), state2.last_crates()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day5::Problem;
+
+    const SAMPLE: &str = r#"    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2"#;
+
+    #[test]
+    fn can_parse_problem() {
+        assert!(SAMPLE.parse::<Problem>().is_ok());
+    }
+
+    #[test]
+    fn reports_invalid_problems() {
+        let program = "[Z] [M]\n 1   2 \n\nmove 1 from 2 to 3";
+        let err = program.parse::<Problem>().unwrap_err();
+        assert_eq!(
+            "day 5, line 4, column 18: unknown stack: '3'",
+            err.to_string()
+        );
+
+        let program = "[Z] [M]\n 1   2 \n\nmove one from 2 to 1";
+        let err = program.parse::<Problem>().unwrap_err();
+        assert_eq!(
+            "day 5, line 4, column 6: expected digit: 'move one from 2 to 1'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn reports_moves_of_missing_crates() {
+        let program = "[A]\n 1 \n\nmove 2 from 1 to 1";
+        let err = program.parse::<Problem>().unwrap_err();
+        assert_eq!(
+            "day 5, line 4, column 6: not enough crates in the stack: '2'",
+            err.to_string()
+        );
+
+        // the second move takes crates the first one brought.
+        let program = "    [B]\n[A] [C]\n 1   2 \n\nmove 2 from 2 to 1\nmove 3 from 1 to 2";
+        assert!(program.parse::<Problem>().is_ok());
+        let program = "    [B]\n[A] [C]\n 1   2 \n\nmove 2 from 2 to 1\nmove 3 from 2 to 1";
+        let err = program.parse::<Problem>().unwrap_err();
+        assert_eq!(
+            "day 5, line 6, column 6: not enough crates in the stack: '3'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn can_run_problem() {
+        let mut problem = SAMPLE.parse::<Problem>().unwrap();
+        assert_eq!("ZN MCD P", problem.state.to_string());
+        problem.run();
+        assert_eq!("C M PDNZ", problem.state.to_string());
+        assert_eq!("CMZ".to_owned(), problem.last_crates());
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::{HashSet, VecDeque};
+use std::io::BufRead;
+
+use itertools::Itertools;
+
+use crate::{debug, trace, warn, Error, Result, Solution};
+
+#[derive(Debug, PartialEq)]
+pub struct Window;
+
+impl Window {
+    pub fn find_marker(input: &str) -> usize {
+        for (fst, snd, thrd, fourth) in input.bytes().enumerate().tuple_windows() {
+            let set = HashSet::from([fst.1, snd.1, thrd.1, fourth.1]);
+            trace!("window {}..={}: {set:?}", fst.0 + 1, fourth.0 + 1);
+            if set.len() == 4 {
+                debug!("start-of-packet marker ends at {}", fourth.0 + 1);
+                return fourth.0 + 1;
+            }
+        }
+        warn!("no start-of-packet marker in the signal");
+        0
+    }
+
+    pub fn find_message_marker(input: &str) -> usize {
+        for (start, window) in input.as_bytes().windows(14).enumerate() {
+            let set: HashSet<u8> = window.iter().copied().collect();
+            let end = start + window.len();
+            trace!("window {}..={end}: {} distinct", start + 1, set.len());
+            if set.len() == 14 {
+                debug!("start-of-message marker ends at {end}");
+                // return the index of the end of the current window.
+                return end;
+            }
+        }
+        warn!("no start-of-message marker in the signal");
+        0
+    }
+}
+
+/// Follows the position of each byte of a signal. Signals may only hold ASCII characters,
+/// so that a byte is a character whether the signal is parsed or streamed.
+#[derive(Debug, Default)]
+struct Cursor {
+    line: usize,
+    column: usize,
+}
+
+impl Cursor {
+    fn advance(&mut self, byte: u8) -> Result<()> {
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 0;
+            return Ok(());
+        }
+
+        self.column += 1;
+        if !byte.is_ascii() {
+            let text = format!("\\x{byte:02x}");
+            return Err(
+                Error::parse(6, self.column, &text, "not an ASCII character")
+                    .on_line(self.line + 1),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Finds a marker in a signal read one character at a time, only keeping the last
+/// `size` characters.
+#[derive(Debug)]
+pub struct MarkerFinder {
+    size: usize,
+    window: VecDeque<u8>,
+    counts: [u8; 256],
+    /// Number of characters that appear more than once in the window.
+    duplicates: usize,
+    position: usize,
+    marker: Option<usize>,
+}
+
+impl MarkerFinder {
+    pub fn new(size: usize) -> MarkerFinder {
+        MarkerFinder {
+            size,
+            window: VecDeque::with_capacity(size + 1),
+            counts: [0; 256],
+            duplicates: 0,
+            position: 0,
+            marker: None,
+        }
+    }
+
+    /// Adds the next character of the signal, and returns the marker once it is found.
+    pub fn push(&mut self, character: u8) -> Option<usize> {
+        if self.marker.is_some() {
+            return self.marker;
+        }
+
+        self.position += 1;
+        self.window.push_back(character);
+        self.counts[character as usize] += 1;
+        if self.counts[character as usize] == 2 {
+            self.duplicates += 1;
+        }
+        if self.window.len() > self.size {
+            let oldest = self.window.pop_front().unwrap() as usize;
+            self.counts[oldest] -= 1;
+            if self.counts[oldest] == 1 {
+                self.duplicates -= 1;
+            }
+        }
+
+        trace!(
+            "window of {} at {}: {} duplicates",
+            self.size,
+            self.position,
+            self.duplicates
+        );
+        if self.window.len() == self.size && self.duplicates == 0 {
+            debug!("marker of {} ends at {}", self.size, self.position);
+            self.marker = Some(self.position);
+        }
+        self.marker
+    }
+
+    /// The position of the end of the marker, or 0 when it has not been found.
+    pub fn marker(&self) -> usize {
+        if self.marker.is_none() {
+            warn!("no marker of {} in the signal", self.size);
+        }
+        self.marker.unwrap_or(0)
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(mut reader: impl BufRead) -> Result<Self::Input> {
+        let mut signal = vec![];
+        reader.read_to_end(&mut signal)?;
+        let mut cursor = Cursor::default();
+        for &byte in &signal {
+            cursor.advance(byte)?;
+        }
+
+        Ok(String::from_utf8(signal).expect("an ASCII signal"))
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        Window::find_marker(input)
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        Window::find_message_marker(input)
+    }
+
+    /// Keeps checking the rest of the signal once both markers are found, so that it is
+    /// rejected like when it is parsed.
+    fn stream(reader: impl BufRead) -> Result<(usize, usize)> {
+        let mut packet = MarkerFinder::new(4);
+        let mut message = MarkerFinder::new(14);
+        let mut cursor = Cursor::default();
+        for character in reader.bytes() {
+            let character = character?;
+            cursor.advance(character)?;
+            packet.push(character);
+            message.push(character);
+        }
+
+        Ok((packet.marker(), message.marker()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day6::{Day6, MarkerFinder, Window};
+    use crate::Solution;
+
+    #[test]
+    fn can_find_first_mark() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(7, Window::find_marker(input));
+
+        let input2 = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+        assert_eq!(5, Window::find_marker(input2));
+
+        let input3 = "nppdvjthqldpwncqszvftbrmjlhg";
+        assert_eq!(6, Window::find_marker(input3));
+
+        let input4 = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+        assert_eq!(10, Window::find_marker(input4));
+
+        let input5 = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+        assert_eq!(11, Window::find_marker(input5));
+    }
+
+    #[test]
+    fn can_find_message_marker() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(19, Window::find_message_marker(input));
+
+        let input2 = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+        assert_eq!(23, Window::find_message_marker(input2));
+
+        let input3 = "nppdvjthqldpwncqszvftbrmjlhg";
+        assert_eq!(23, Window::find_message_marker(input3));
+
+        let input4 = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+        assert_eq!(29, Window::find_message_marker(input4));
+
+        let input5 = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+        assert_eq!(26, Window::find_message_marker(input5));
+    }
+
+    #[test]
+    fn can_find_markers_while_reading() {
+        let mut finder = MarkerFinder::new(4);
+        let markers: Vec<Option<usize>> = "mjqjpqmgb".bytes().map(|c| finder.push(c)).collect();
+
+        assert_eq!(vec![None; 6], markers[..6]);
+        assert_eq!(vec![Some(7); 3], markers[6..]);
+        assert_eq!(0, MarkerFinder::new(14).marker());
+    }
+
+    #[test]
+    fn can_parse_and_stream_alike() {
+        let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n";
+        let signal = Day6::parse(input.as_bytes()).unwrap();
+        assert_eq!(
+            (Day6::part1(&signal), Day6::part2(&signal)),
+            Day6::stream(input.as_bytes()).unwrap()
+        );
+
+        // é is two bytes, and the message marker would end before it or after it.
+        let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgv\u{e9}jw\n";
+        let parsed = Day6::parse(input.as_bytes()).unwrap_err();
+        let streamed = Day6::stream(input.as_bytes()).unwrap_err();
+        assert_eq!(
+            "day 6, line 1, column 31: not an ASCII character: '\\xc3'",
+            parsed.to_string()
+        );
+        assert_eq!(parsed.to_string(), streamed.to_string());
+    }
+}
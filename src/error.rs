@@ -0,0 +1,207 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Errors produced while reading or parsing a puzzle input, or the files configuring
+/// a command.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The input file of a puzzle could not be opened.
+    Input {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// The input does not have the shape the puzzle expects.
+    ///
+    /// `line` and `column` are 1-based, and `text` is the offending fragment of the input.
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    /// A sum of the input does not fit in the integer type it is computed with.
+    ///
+    /// `line` is 1-based, and `elf` is the 0-based index of the elf whose sum overflows.
+    Overflow {
+        day: u8,
+        line: usize,
+        elf: usize,
+        width: &'static str,
+    },
+    /// A file configuring a command, like scoring rules, is invalid.
+    ///
+    /// `path` is missing when the configuration was not read from a file, and `line` is
+    /// 1-based, missing when the error is not about a single line.
+    Config {
+        path: Option<PathBuf>,
+        line: Option<usize>,
+        reason: String,
+    },
+    /// An error in one of the files a command reads, like the guides of a league.
+    File {
+        path: PathBuf,
+        source: Box<Error>,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Builds a parse error located in the first line of the input. Parsers that work
+    /// on a single line use it and let their callers fix the line with [`Error::on_line`].
+    pub fn parse(day: u8, column: usize, text: &str, reason: impl Into<String>) -> Error {
+        Error::Parse {
+            day,
+            line: 1,
+            column,
+            text: text.to_owned(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Builds a configuration error that is not located in a file yet.
+    pub fn config(reason: impl Into<String>) -> Error {
+        Error::Config {
+            path: None,
+            line: None,
+            reason: reason.into(),
+        }
+    }
+
+    /// Moves a parse or configuration error to the given 1-based line.
+    pub fn on_line(self, line_number: usize) -> Error {
+        match self {
+            Error::Config { path, reason, .. } => Error::Config {
+                path,
+                line: Some(line_number),
+                reason,
+            },
+            Error::Parse {
+                day,
+                column,
+                text,
+                reason,
+                ..
+            } => Error::Parse {
+                day,
+                line: line_number,
+                column,
+                text,
+                reason,
+            },
+            err => err,
+        }
+    }
+
+    /// Moves a parse error down by `lines`, for inputs parsed from the middle of a file.
+    pub fn after_lines(self, lines: usize) -> Error {
+        match self {
+            Error::Parse { line, .. } => self.on_line(line + lines),
+            Error::Overflow {
+                day,
+                line,
+                elf,
+                width,
+            } => Error::Overflow {
+                day,
+                line: line + lines,
+                elf,
+                width,
+            },
+            err => err,
+        }
+    }
+
+    /// Tells which file an error comes from, for errors read from a file given by its path.
+    pub fn in_file(self, path: &Path) -> Error {
+        match self {
+            Error::Io(source) => Error::Input {
+                path: path.to_owned(),
+                source,
+            },
+            Error::Config { line, reason, .. } => Error::Config {
+                path: Some(path.to_owned()),
+                line,
+                reason,
+            },
+            err @ (Error::Parse { .. } | Error::Overflow { .. }) => Error::File {
+                path: path.to_owned(),
+                source: Box::new(err),
+            },
+            err => err,
+        }
+    }
+}
+
+/// Returns the 1-based column where `fragment` starts, when it is a slice of `line`.
+pub(crate) fn column_of(line: &str, fragment: &str) -> usize {
+    let offset = (fragment.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line.get(..offset.min(line.len()))
+        .map_or(1, |prefix| prefix.chars().count() + 1)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{err}"),
+            Error::Input { path, source } => {
+                write!(f, "cannot read input file {}: {source}", path.display())?;
+                if source.kind() == io::ErrorKind::NotFound {
+                    write!(
+                        f,
+                        " (pass the input path, `-` for stdin, or set AOC_INPUT_DIR)"
+                    )?;
+                }
+                Ok(())
+            }
+            Error::Parse {
+                day,
+                line,
+                column,
+                text,
+                reason,
+            } => write!(
+                f,
+                "day {day}, line {line}, column {column}: {reason}: '{text}'"
+            ),
+            Error::Overflow {
+                day,
+                line,
+                elf,
+                width,
+            } => write!(
+                f,
+                "day {day}, line {line}: the calories of elf {elf} do not fit in a {width}"
+            ),
+            Error::Config { path, line, reason } => {
+                match (path, line) {
+                    (Some(path), Some(line)) => write!(f, "{}, line {line}: ", path.display())?,
+                    (Some(path), None) => write!(f, "{}: ", path.display())?,
+                    (None, Some(line)) => write!(f, "line {line}: ")?,
+                    (None, None) => {}
+                }
+                write!(f, "{reason}")
+            }
+            Error::File { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) | Error::Input { source: err, .. } => Some(err),
+            Error::File { source, .. } => Some(source.as_ref()),
+            Error::Parse { .. } | Error::Overflow { .. } | Error::Config { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
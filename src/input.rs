@@ -0,0 +1,108 @@
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use crate::{Error, Result};
+
+/// Environment variable naming the directory that holds the `inputN` files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// The directory holding the puzzle inputs: `$AOC_INPUT_DIR`, or `inputs/`.
+pub fn input_dir() -> PathBuf {
+    input_dir_from(env::var_os(INPUT_DIR_VAR))
+}
+
+fn input_dir_from(input_dir: Option<OsString>) -> PathBuf {
+    input_dir
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR), PathBuf::from)
+}
+
+/// Where the input of a puzzle is read from.
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Resolves the input of a day: an explicit `path` wins, `-` meaning stdin.
+    /// Otherwise the `inputN` file is looked up in `$AOC_INPUT_DIR`, or in `inputs/`.
+    pub fn resolve(day: u8, path: Option<&str>) -> InputSource {
+        InputSource::from_parts(day, path, env::var_os(INPUT_DIR_VAR))
+    }
+
+    fn from_parts(day: u8, path: Option<&str>, input_dir: Option<OsString>) -> InputSource {
+        match path {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => InputSource::File(input_dir_from(input_dir).join(format!("input{day}"))),
+        }
+    }
+
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::File(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(source) => Err(Error::Input {
+                    path: path.clone(),
+                    source,
+                }),
+            },
+        }
+    }
+
+    pub fn read_to_string(&self) -> Result<String> {
+        let mut input = String::new();
+        self.open()?.read_to_string(&mut input)?;
+        Ok(input)
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::input::InputSource;
+
+    #[test]
+    fn can_resolve_input_sources() {
+        let source = InputSource::from_parts(3, Some("-"), Some("elsewhere".into()));
+        assert_eq!(InputSource::Stdin, source);
+
+        let source = InputSource::from_parts(3, Some("my/input"), Some("elsewhere".into()));
+        assert_eq!(InputSource::File(PathBuf::from("my/input")), source);
+
+        let source = InputSource::from_parts(3, None, Some("elsewhere".into()));
+        assert_eq!(InputSource::File(PathBuf::from("elsewhere/input3")), source);
+
+        let source = InputSource::from_parts(3, None, None);
+        assert_eq!(InputSource::File(PathBuf::from("inputs/input3")), source);
+    }
+
+    #[test]
+    fn reports_missing_files() {
+        let source = InputSource::File(PathBuf::from("inputs/input99"));
+        let err = source.open().err().unwrap();
+
+        assert!(err
+            .to_string()
+            .starts_with("cannot read input file inputs/input99"));
+        assert!(err.to_string().contains("AOC_INPUT_DIR"));
+    }
+}
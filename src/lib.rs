@@ -0,0 +1,19 @@
+//! Advent of Code 2022 solutions, shared by the per-day binaries and the `aoc` runner.
+
+pub mod answers;
+pub mod bench;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod error;
+pub mod input;
+pub mod log;
+pub mod output;
+pub mod records;
+pub mod solution;
+
+pub use error::{Error, Result};
+pub use solution::{puzzle, puzzles, run, Solution};
@@ -0,0 +1,150 @@
+//! A small logging layer writing to stderr, so the days can trace their parsing
+//! decisions and intermediate state without getting in the way of the answers.
+//!
+//! The level is global and set once by the binaries from `-v`, `-vv` and `--quiet`.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    /// Suspicious inputs that can still be solved. This is the default level.
+    Warn,
+    /// Parsing decisions, like how many records were read.
+    Debug,
+    /// Intermediate state, like the stacks of day 5 after every move.
+    Trace,
+}
+
+impl Level {
+    fn from_u8(level: u8) -> Level {
+        match level {
+            0 => Level::Error,
+            1 => Level::Warn,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Error => write!(f, "error"),
+            Level::Warn => write!(f, "warn"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+/// Raises a verbosity by `count` levels, up to the most verbose one.
+fn raise(verbosity: u8, count: usize) -> u8 {
+    let count = u8::try_from(count).unwrap_or(u8::MAX);
+    verbosity.saturating_add(count).min(Level::Trace as u8)
+}
+
+/// Splits the verbosity flags from the other arguments: every `-v` raises the level
+/// (`-vv` counts twice), while `-q`/`--quiet` only keeps the errors.
+pub fn take_verbosity(args: impl IntoIterator<Item = String>) -> (Level, Vec<String>) {
+    let mut verbosity = Level::Warn as u8;
+    let mut quiet = false;
+    let mut rest = vec![];
+    for arg in args {
+        match arg.as_str() {
+            "-q" | "--quiet" => quiet = true,
+            "--verbose" => verbosity = raise(verbosity, 1),
+            _ if arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v') => {
+                verbosity = raise(verbosity, arg.len() - 1)
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    let level = if quiet {
+        Level::Error
+    } else {
+        Level::from_u8(verbosity)
+    };
+    (level, rest)
+}
+
+/// Writes a message to stderr if `level` is enabled. Use the macros instead, which
+/// name the module the message comes from.
+pub fn log(level: Level, module: &str, message: fmt::Arguments) {
+    if enabled(level) {
+        let module = module.strip_prefix("aoc2022::").unwrap_or(module);
+        eprintln!("{level} {module}: {message}");
+    }
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        $crate::log::log($crate::log::Level::Warn, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::log::log($crate::log::Level::Debug, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::log::log($crate::log::Level::Trace, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::log::{take_verbosity, Level};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn can_take_verbosity_flags() {
+        let (level, rest) = take_verbosity(args(&["--day", "5", "-v"]));
+        assert_eq!(Level::Debug, level);
+        assert_eq!(args(&["--day", "5"]), rest);
+
+        let (level, _) = take_verbosity(args(&["-vv", "-"]));
+        assert_eq!(Level::Trace, level);
+
+        let (level, rest) = take_verbosity(args(&["-q", "-"]));
+        assert_eq!(Level::Error, level);
+        assert_eq!(args(&["-"]), rest);
+
+        let (level, rest) = take_verbosity(args(&["all", "vv"]));
+        assert_eq!(Level::Warn, level);
+        assert_eq!(args(&["all", "vv"]), rest);
+
+        let (level, _) = take_verbosity(args(&[]));
+        assert_eq!(Level::Warn, level);
+
+        // however many there are, the flags stop at the most verbose level.
+        let flag = format!("-{}", "v".repeat(300));
+        let (level, _) = take_verbosity(vec![flag.clone(), flag, "--verbose".to_owned()]);
+        assert_eq!(Level::Trace, level);
+    }
+}
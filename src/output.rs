@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use crate::solution::PartAnswer;
+
+/// How answers are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Format {
+    /// `Part N: answer` lines, meant for people.
+    #[default]
+    Text,
+    /// One `{day, part, answer, elapsed_ms}` JSON record per line, meant for tools.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format '{s}', expected text or json")),
+        }
+    }
+}
+
+/// Renders a single answer in the given format, without a trailing newline.
+pub fn render(answer: &PartAnswer, format: Format) -> String {
+    match format {
+        Format::Text => format!("Part {}: {}", answer.part, answer.answer),
+        Format::Json => serde_json::to_string(answer).expect("answers are always valid JSON"),
+    }
+}
+
+pub fn print_answers(answers: &[PartAnswer], format: Format) {
+    for answer in answers {
+        println!("{}", render(answer, format));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{render, Format};
+    use crate::solution::PartAnswer;
+
+    #[test]
+    fn can_render_answers() {
+        let answer = PartAnswer {
+            day: 5,
+            part: 1,
+            answer: "CMZ".to_owned(),
+            elapsed_ms: 0.25,
+        };
+
+        assert_eq!("Part 1: CMZ", render(&answer, Format::Text));
+        assert_eq!(
+            r#"{"day":5,"part":1,"answer":"CMZ","elapsed_ms":0.25}"#,
+            render(&answer, Format::Json)
+        );
+        assert_eq!(Ok(Format::Json), "json".parse());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! Reader for paragraph-style inputs, where records are grouped by blank lines.
+
+use std::io::{BufRead, Lines};
+
+use crate::Result;
+
+/// Iterator over the groups of records of an input, built by [`groups`].
+pub struct Groups<R, F> {
+    lines: Lines<R>,
+    line_number: usize,
+    group_start: usize,
+    parse: F,
+}
+
+/// Reads the groups of records of `reader`, parsing each non-blank line with `parse`.
+///
+/// Groups are separated by one or more blank lines, and the last group does not need to
+/// be followed by one. Lines may end with `\n` or `\r\n`. Parse errors are moved to the
+/// line they were found on.
+pub fn groups<R, T, F>(reader: R, parse: F) -> Groups<R, F>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<T>,
+{
+    Groups {
+        lines: reader.lines(),
+        line_number: 0,
+        group_start: 0,
+        parse,
+    }
+}
+
+impl<R, F> Groups<R, F> {
+    /// The 1-based line of the first record of the last group read. As groups have no
+    /// blank lines, the records of the group are on the lines that follow.
+    pub fn group_start(&self) -> usize {
+        self.group_start
+    }
+}
+
+impl<R, T, F> Iterator for Groups<R, F>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<T>,
+{
+    type Item = Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut group = vec![];
+        for line in self.lines.by_ref() {
+            self.line_number += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err.into())),
+            };
+
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                if group.is_empty() {
+                    continue;
+                }
+                return Some(Ok(group));
+            }
+
+            if group.is_empty() {
+                self.group_start = self.line_number;
+            }
+            match (self.parse)(line) {
+                Ok(record) => group.push(record),
+                Err(err) => return Some(Err(err.on_line(self.line_number))),
+            }
+        }
+
+        if group.is_empty() {
+            None
+        } else {
+            Some(Ok(group))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::records::groups;
+    use crate::{Error, Result};
+
+    fn parse_number(line: &str) -> Result<u32> {
+        line.parse()
+            .map_err(|_| Error::parse(1, 1, line, "expected a number"))
+    }
+
+    #[test]
+    fn can_read_groups() {
+        let input = "\n1\n2\n\n\n\n3\r\n\r\n4\n5";
+        let records: Vec<Vec<u32>> = groups(input.as_bytes(), parse_number)
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(vec![vec![1, 2], vec![3], vec![4, 5]], records);
+
+        let mut reader = groups(input.as_bytes(), parse_number);
+        reader.nth(1);
+        assert_eq!(7, reader.group_start());
+    }
+
+    #[test]
+    fn reports_invalid_records() {
+        let input = "1\n\n2\nx\n";
+        let err = groups(input.as_bytes(), parse_number)
+            .collect::<Result<Vec<_>>>()
+            .unwrap_err();
+
+        assert_eq!(
+            "day 1, line 4, column 1: expected a number: 'x'",
+            err.to_string()
+        );
+    }
+}
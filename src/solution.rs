@@ -0,0 +1,244 @@
+use std::env;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::marker::PhantomData;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::input::InputSource;
+use crate::log;
+use crate::output::{self, Format};
+use crate::Result;
+
+/// A puzzle solution: the input is parsed once into a typed value and both
+/// parts are computed from it.
+pub trait Solution {
+    /// Day of the advent calendar solved by this type.
+    const DAY: u8;
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// Solves both parts in a single pass over the input, keeping a bounded amount of
+    /// it in memory. The default implementation parses the whole input first.
+    fn stream(reader: impl BufRead) -> Result<(Self::Part1, Self::Part2)> {
+        let input = Self::parse(reader)?;
+        Ok((Self::part1(&input), Self::part2(&input)))
+    }
+}
+
+/// Object safe view of a [`Solution`], so the day to run can be picked at runtime.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Box<dyn ParsedInput>>;
+
+    /// Solves both parts with [`Solution::stream`].
+    fn stream(&self, reader: &mut dyn BufRead) -> Result<(String, String)>;
+}
+
+/// The typed input of a [`Solution`], with its answers rendered as text.
+pub trait ParsedInput {
+    fn part1(&self) -> String;
+
+    fn part2(&self) -> String;
+}
+
+/// The answer to one part of a puzzle, and the time it took to compute it from the
+/// parsed input.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PartAnswer {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub elapsed_ms: f64,
+}
+
+/// Parses the input of a puzzle and solves the requested parts, timing each of them.
+pub fn solve(
+    puzzle: &dyn Puzzle,
+    reader: &mut dyn BufRead,
+    parts: &[u8],
+) -> Result<Vec<PartAnswer>> {
+    let parsed = puzzle.parse(reader)?;
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => parsed.part1(),
+                _ => parsed.part2(),
+            };
+            PartAnswer {
+                day: puzzle.day(),
+                part,
+                answer,
+                elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+            }
+        })
+        .collect();
+
+    Ok(answers)
+}
+
+/// Solves the requested parts in a single pass over the input with [`Puzzle::stream`].
+/// As both parts are solved together, each answer reports the time of the whole pass.
+pub fn stream(
+    puzzle: &dyn Puzzle,
+    reader: &mut dyn BufRead,
+    parts: &[u8],
+) -> Result<Vec<PartAnswer>> {
+    let start = Instant::now();
+    let (part1, part2) = puzzle.stream(reader)?;
+    let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let answers = parts
+        .iter()
+        .map(|&part| PartAnswer {
+            day: puzzle.day(),
+            part,
+            answer: if part == 1 {
+                part1.clone()
+            } else {
+                part2.clone()
+            },
+            elapsed_ms,
+        })
+        .collect();
+
+    Ok(answers)
+}
+
+struct Erased<S>(PhantomData<S>);
+
+impl<S> Puzzle for Erased<S>
+where
+    S: Solution + Sync + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Box<dyn ParsedInput>> {
+        let input = S::parse(reader)?;
+        Ok(Box::new(Parsed::<S>(input)))
+    }
+
+    fn stream(&self, reader: &mut dyn BufRead) -> Result<(String, String)> {
+        let (part1, part2) = S::stream(reader)?;
+        Ok((part1.to_string(), part2.to_string()))
+    }
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> ParsedInput for Parsed<S> {
+    fn part1(&self) -> String {
+        S::part1(&self.0).to_string()
+    }
+
+    fn part2(&self) -> String {
+        S::part2(&self.0).to_string()
+    }
+}
+
+static PUZZLES: [&dyn Puzzle; 6] = [
+    &Erased::<crate::day1::Day1>(PhantomData),
+    &Erased::<crate::day2::Day2>(PhantomData),
+    &Erased::<crate::day3::Day3>(PhantomData),
+    &Erased::<crate::day4::Day4>(PhantomData),
+    &Erased::<crate::day5::Day5>(PhantomData),
+    &Erased::<crate::day6::Day6>(PhantomData),
+];
+
+/// Every solved puzzle, ordered by day.
+pub fn puzzles() -> &'static [&'static dyn Puzzle] {
+    &PUZZLES
+}
+
+/// Returns the puzzle for the given day, if it has been solved.
+pub fn puzzle(day: u8) -> Option<&'static dyn Puzzle> {
+    PUZZLES.iter().copied().find(|puzzle| puzzle.day() == day)
+}
+
+const RUN_USAGE: &str = "usage: dayN [-v | -vv | --quiet] [--format <text|json>] [PATH | -]";
+
+/// Solves both parts of `S` and prints the answers, or the reason why the input could
+/// not be solved. The command line takes the verbosity flags, an optional `--format`
+/// and the path of the input, which is resolved by [`InputSource::resolve`].
+pub fn run<S: Solution + Sync + 'static>() -> ExitCode {
+    let mut format = Format::default();
+    let mut path = None;
+
+    let (level, args) = log::take_verbosity(env::args().skip(1));
+    log::set_level(level);
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let parsed = match arg.as_str() {
+            "--format" | "-f" => args
+                .next()
+                .ok_or("missing value for --format".to_owned())
+                .and_then(|value| value.parse())
+                .map(|value| format = value),
+            _ if path.is_none() => {
+                path = Some(arg);
+                Ok(())
+            }
+            _ => Err(format!("unexpected argument '{arg}'")),
+        };
+
+        if let Err(message) = parsed {
+            eprintln!("error: {message}\n{RUN_USAGE}");
+            return ExitCode::from(2);
+        }
+    }
+
+    let puzzle = Erased::<S>(PhantomData);
+    let answers = InputSource::resolve(S::DAY, path.as_deref())
+        .open()
+        .and_then(|mut reader| solve(&puzzle, &mut reader, &[1, 2]));
+
+    match answers {
+        Ok(answers) => {
+            output::print_answers(&answers, format);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::{puzzle, puzzles};
+
+    #[test]
+    fn puzzles_are_ordered_by_day() {
+        let days: Vec<u8> = puzzles().iter().map(|puzzle| puzzle.day()).collect();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], days);
+    }
+
+    #[test]
+    fn can_solve_through_puzzle() {
+        let day6 = puzzle(6).unwrap();
+        let input = day6
+            .parse(&mut "mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes())
+            .unwrap();
+
+        assert_eq!("7", input.part1());
+        assert_eq!("19", input.part2());
+        assert!(puzzle(7).is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! Runs every fixture in `examples/dayN/*.txt` through the solver of its day.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc2022::answers::ExpectedAnswers;
+use aoc2022::solution;
+
+const SEPARATOR: &str = "\n---\n";
+
+struct Fixture {
+    path: PathBuf,
+    day: u8,
+    expected: ExpectedAnswers,
+    input: String,
+}
+
+impl Fixture {
+    fn load(path: &Path, day: u8) -> Result<Fixture, String> {
+        let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        let (header, input) = content
+            .split_once(SEPARATOR)
+            .ok_or("missing the '---' line after the expected answers")?;
+        let expected = header.parse().map_err(|err| format!("{err}"))?;
+
+        Ok(Fixture {
+            path: path.to_owned(),
+            day,
+            expected,
+            input: input.to_owned(),
+        })
+    }
+}
+
+/// Finds the fixtures of every `dayN` directory, sorted by path.
+fn discover(examples: &Path) -> Vec<Result<Fixture, String>> {
+    let mut paths = vec![];
+    for dir in fs::read_dir(examples).unwrap() {
+        let dir = dir.unwrap().path();
+        let day = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("day"))
+            .and_then(|day| day.parse::<u8>().ok());
+        let Some(day) = day else { continue };
+
+        for file in fs::read_dir(&dir).unwrap() {
+            let path = file.unwrap().path();
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                paths.push((path, day));
+            }
+        }
+    }
+
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|(path, day)| {
+            Fixture::load(&path, day).map_err(|err| format!("{}: {err}", path.display()))
+        })
+        .collect()
+}
+
+/// Checks the answers of both the parsed and the streaming solver.
+fn check(fixture: &Fixture) -> Result<(), String> {
+    let puzzle = aoc2022::puzzle(fixture.day).ok_or("the day is not solved yet")?;
+    let solved = solution::solve(puzzle, &mut fixture.input.as_bytes(), &[1, 2])
+        .map_err(|err| err.to_string())?;
+    let streamed = solution::stream(puzzle, &mut fixture.input.as_bytes(), &[1, 2])
+        .map_err(|err| format!("streaming: {err}"))?;
+    for (solved, streamed) in solved.iter().zip(&streamed) {
+        if solved.answer != streamed.answer {
+            return Err(format!(
+                "part {} is {} when streaming, but {} when parsed",
+                solved.part, streamed.answer, solved.answer
+            ));
+        }
+    }
+    let answers = solved;
+
+    let mut checked = 0;
+    for answer in answers {
+        if let Some(expected) = fixture.expected.expected(answer.part) {
+            if expected != answer.answer {
+                return Err(format!(
+                    "part {} is {}, expected {expected}",
+                    answer.part, answer.answer
+                ));
+            }
+            checked += 1;
+        }
+    }
+
+    match checked {
+        0 => Err("no expected answers".to_owned()),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn examples_match_expected_answers() {
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+    let fixtures = discover(&examples);
+    assert!(
+        !fixtures.is_empty(),
+        "no fixtures in {}",
+        examples.display()
+    );
+
+    let failures: Vec<String> = fixtures
+        .iter()
+        .filter_map(|fixture| match fixture {
+            Ok(fixture) => check(fixture)
+                .err()
+                .map(|err| format!("{}: {err}", fixture.path.display())),
+            Err(err) => Some(err.clone()),
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}